@@ -0,0 +1,134 @@
+//! This module provides the functionality used to run some basic accessibility
+//! checks on the rendered index document, so that obvious issues introduced by
+//! template or settings changes (i.e. custom code) can be caught.
+
+use super::warnings::{self, WarningKind};
+use anyhow::{format_err, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::{fmt, fs, path::Path};
+use tracing::{debug, instrument};
+
+lazy_static! {
+    /// Regular expression used to remove the script elements from the document.
+    static ref HTML_SCRIPT: Regex = Regex::new(r"(?is)<script\b[^>]*>.*?</script>")
+        .expect("expr in HTML_SCRIPT to be valid");
+
+    /// Regular expression used to find the html element start tag.
+    static ref HTML_HTML: Regex = Regex::new(r"(?i)<html\b[^>]*>").expect("expr in HTML_HTML to be valid");
+
+    /// Regular expression used to find the document title.
+    static ref HTML_TITLE: Regex = Regex::new(r"(?is)<title\b[^>]*>(?P<title>.*?)</title>")
+        .expect("expr in HTML_TITLE to be valid");
+
+    /// Regular expression used to find the image elements.
+    static ref HTML_IMG: Regex = Regex::new(r"(?i)<img\b[^>]*>").expect("expr in HTML_IMG to be valid");
+
+    /// Regular expression used to extract the attributes of an HTML tag
+    /// (attributes may not have a value).
+    static ref HTML_ATTR: Regex =
+        Regex::new(r#"\s(?P<name>[a-zA-Z:-]+)(?:\s*=\s*(?:"(?P<v1>[^"]*)"|'(?P<v2>[^']*)'|(?P<v3>[^\s"'>]+)))?"#)
+            .expect("expr in HTML_ATTR to be valid");
+}
+
+/// Severity of an accessibility finding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// Accessibility finding.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Finding {
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// Check the rendered index document for some accessibility issues. Findings
+/// with warning severity are added to the warnings collection, whereas the
+/// ones with error severity make the check fail.
+#[instrument(skip_all, err)]
+pub(crate) fn check_index_accessibility(output_dir: &Path) -> Result<()> {
+    debug!("checking index document accessibility");
+
+    let html = fs::read_to_string(output_dir.join("index.html"))?;
+    let findings = find_accessibility_issues(&html);
+
+    let mut errors = vec![];
+    for finding in findings {
+        match finding.severity {
+            Severity::Error => errors.push(format!("- [{}] {}", finding.severity, finding.message)),
+            Severity::Warning => {
+                let msg = format!("[{}] {}", finding.severity, finding.message);
+                warnings::add(WarningKind::Accessibility, Some("index.html"), msg);
+            }
+        }
+    }
+    if !errors.is_empty() {
+        return Err(format_err!(
+            "accessibility check of index.html failed:\n{}",
+            errors.join("\n")
+        ));
+    }
+
+    Ok(())
+}
+
+/// Find some accessibility issues in the HTML document provided.
+fn find_accessibility_issues(html: &str) -> Vec<Finding> {
+    let mut findings = vec![];
+    let html = HTML_SCRIPT.replace_all(html, "");
+
+    // The document language must be declared
+    let lang = HTML_HTML.find(&html).and_then(|tag| get_attribute(tag.as_str(), "lang"));
+    if lang.map_or(true, |lang| lang.trim().is_empty()) {
+        findings.push(Finding {
+            severity: Severity::Error,
+            message: "html element has no lang attribute".to_string(),
+        });
+    }
+
+    // The document must have a title
+    let title = HTML_TITLE.captures(&html).map(|c| c["title"].trim().to_string());
+    if title.map_or(true, |title| title.is_empty()) {
+        findings.push(Finding {
+            severity: Severity::Error,
+            message: "document has no title".to_string(),
+        });
+    }
+
+    // Images must provide an alternative text (it can be empty for decorative
+    // images, but the attribute must be present)
+    for img in HTML_IMG.find_iter(&html) {
+        if get_attribute(img.as_str(), "alt").is_none() {
+            let src = get_attribute(img.as_str(), "src").unwrap_or_default();
+            findings.push(Finding {
+                severity: Severity::Warning,
+                message: format!("image has no alt attribute (src: {src})"),
+            });
+        }
+    }
+
+    findings
+}
+
+/// Get the value of the attribute provided from the HTML tag given. Attributes
+/// present without a value return an empty string.
+fn get_attribute(tag: &str, name: &str) -> Option<String> {
+    HTML_ATTR.captures_iter(tag).find(|c| c["name"].eq_ignore_ascii_case(name)).map(|c| {
+        c.name("v1")
+            .or_else(|| c.name("v2"))
+            .or_else(|| c.name("v3"))
+            .map_or(String::new(), |v| v.as_str().to_string())
+    })
+}
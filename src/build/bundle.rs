@@ -0,0 +1,88 @@
+//! This module provides the functionality used to build a landscape from a
+//! packaged bundle (.tar.gz), which contains all the sources required to build
+//! it (data file, settings, logos and, optionally, the guide).
+//!
+//! The bundle is expected to have the following layout:
+//!
+//! ```text
+//! landscape.yml
+//! settings.yml
+//! guide.yml      (optional)
+//! logos/
+//! ```
+
+use crate::{DataSource, GuideSource, LogosSource, SettingsSource};
+use anyhow::{format_err, Context, Result};
+use flate2::read::GzDecoder;
+use std::{fs::File, path::Path};
+use tempfile::TempDir;
+use tracing::{debug, instrument};
+
+/// Landscape data file name in the bundle.
+const DATA_FILE: &str = "landscape.yml";
+
+/// Landscape guide file name in the bundle.
+const GUIDE_FILE: &str = "guide.yml";
+
+/// Logos directory in the bundle.
+const LOGOS_PATH: &str = "logos";
+
+/// Landscape settings file name in the bundle.
+const SETTINGS_FILE: &str = "settings.yml";
+
+/// Landscape bundle extracted to a temporary directory. The directory is
+/// removed when the bundle is dropped.
+pub(crate) struct Bundle {
+    pub data_source: DataSource,
+    pub guide_source: GuideSource,
+    pub logos_source: LogosSource,
+    pub settings_source: SettingsSource,
+    _dir: TempDir,
+}
+
+impl Bundle {
+    /// Extract the bundle provided to a temporary directory and setup the
+    /// sources from the files found in it.
+    #[instrument(skip_all, err)]
+    pub(crate) fn extract(bundle: &Path) -> Result<Self> {
+        debug!(?bundle, "extracting landscape bundle");
+
+        // Extract bundle
+        let dir = tempfile::tempdir()?;
+        let file = File::open(bundle).context("error opening landscape bundle")?;
+        tar::Archive::new(GzDecoder::new(file))
+            .unpack(dir.path())
+            .context("error extracting landscape bundle")?;
+
+        // Check required files are present
+        for required_path in [DATA_FILE, SETTINGS_FILE, LOGOS_PATH] {
+            if !dir.path().join(required_path).exists() {
+                return Err(format_err!("{required_path} not found in landscape bundle"));
+            }
+        }
+
+        // Setup sources
+        let guide_file = dir.path().join(GUIDE_FILE);
+        let bundle = Bundle {
+            data_source: DataSource {
+                data_file: Some(dir.path().join(DATA_FILE)),
+                data_url: None,
+            },
+            guide_source: GuideSource {
+                guide_file: guide_file.exists().then_some(guide_file),
+                guide_url: None,
+            },
+            logos_source: LogosSource {
+                logos_path: Some(dir.path().join(LOGOS_PATH)),
+                logos_url: None,
+            },
+            settings_source: SettingsSource {
+                settings_file: Some(dir.path().join(SETTINGS_FILE)),
+                settings_url: None,
+            },
+            _dir: dir,
+        };
+
+        Ok(bundle)
+    }
+}
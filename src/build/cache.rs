@@ -1,29 +1,108 @@
-//! This module defines the cache used to cache files across builds.
+//! This module defines the cache used to cache files across builds, as well
+//! as the different backends available to store them (filesystem, in-memory
+//! and AWS S3).
 
+use crate::CacheBackend;
 use anyhow::{format_err, Result};
-use std::{fs, io::Write, path::PathBuf};
+use async_trait::async_trait;
+use aws_sdk_s3::primitives::ByteStream;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::{
+    collections::HashMap,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
 use tracing::instrument;
 
-/// Path where the cache files will be written to inside the cache directory.
+/// Path where the cache files will be written to inside the cache directory
+/// (or the prefix used for the objects keys when using the S3 backend).
 const CACHE_PATH: &str = "landscape";
 
+lazy_static! {
+    /// Cache namespace regular expression.
+    static ref CACHE_NAMESPACE: Regex = Regex::new("^[a-zA-Z0-9_-]+$").expect("exprs in CACHE_NAMESPACE to be valid");
+}
+
+/// Type alias to represent a Cache trait object.
+pub(crate) type DynCache = Arc<dyn Cache + Send + Sync>;
+
 /// Cache used to store data collected from external services.
+#[async_trait]
+pub(crate) trait Cache {
+    /// List the files available in the cache (name and size in bytes).
+    async fn list(&self) -> Result<Vec<(String, u64)>>;
+
+    /// Read data from the cache file provided if available.
+    async fn read(&self, file_name: &str) -> Result<Option<Vec<u8>>>;
+
+    /// Remove the cache file provided.
+    async fn remove(&self, file_name: &str) -> Result<()>;
+
+    /// Write provided data to cache file.
+    async fn write(&self, file_name: &str, data: &[u8]) -> Result<()>;
+}
+
+/// Create a new cache instance using the backend provided. When a namespace
+/// is provided, the cache files will be stored under it, so that multiple
+/// landscapes can share the same cache without collisions. When the cache is
+/// read-only, nothing will be written to (or removed from) the backend.
+pub(crate) async fn new_cache(
+    backend: CacheBackend,
+    cache_dir: Option<&Path>,
+    s3_bucket: Option<&str>,
+    namespace: Option<&str>,
+    read_only: bool,
+) -> Result<DynCache> {
+    if let Some(namespace) = namespace {
+        if !CACHE_NAMESPACE.is_match(namespace) {
+            return Err(format_err!(
+                "error setting up cache: invalid namespace: {namespace} (only alphanumeric characters, '-' and '_' are allowed)"
+            ));
+        }
+    }
+    let path = match namespace {
+        Some(namespace) => format!("{CACHE_PATH}/{namespace}"),
+        None => CACHE_PATH.to_string(),
+    };
+
+    let cache: DynCache = match backend {
+        CacheBackend::Fs => Arc::new(FsCache::new(cache_dir, &path)?),
+        CacheBackend::Memory => Arc::new(MemoryCache::default()),
+        CacheBackend::S3 => {
+            let Some(bucket) = s3_bucket else {
+                return Err(format_err!("error setting up cache: s3 bucket not provided"));
+            };
+            Arc::new(S3Cache::new(bucket, &path).await)
+        }
+    };
+
+    if read_only {
+        return Ok(Arc::new(ReadOnlyCache { cache }));
+    }
+    Ok(cache)
+}
+
+/// Cache implementation backed by the filesystem.
 #[derive(Debug, Clone, Default)]
-pub(crate) struct Cache {
+pub(crate) struct FsCache {
     cache_dir: PathBuf,
 }
 
-impl Cache {
-    /// Create a new Cache instance.
-    pub(crate) fn new(cache_dir: &Option<PathBuf>) -> Result<Self> {
+impl FsCache {
+    /// Create a new FsCache instance. Files are stored in the path provided
+    /// inside the cache directory.
+    pub(crate) fn new(cache_dir: Option<&Path>, path: &str) -> Result<Self> {
         // Try to use user's cache directory if no cache_dir has been provided
         let cache_dir = match cache_dir {
-            Some(cache_dir) => Some(cache_dir.clone()),
+            Some(cache_dir) => Some(cache_dir.to_path_buf()),
             None => dirs::cache_dir(),
         };
 
         if let Some(mut cache_dir) = cache_dir {
-            cache_dir = cache_dir.join(CACHE_PATH);
+            cache_dir = cache_dir.join(path);
             if !cache_dir.exists() {
                 fs::create_dir_all(&cache_dir)?;
             }
@@ -34,10 +113,27 @@ impl Cache {
             "error setting up cache: no cache directory provided and user's cache directory could not be found"
         ))
     }
+}
 
-    /// Read data from the cache file provided if available.
+#[async_trait]
+impl Cache for FsCache {
+    /// [Cache::list]
+    #[instrument(skip_all, err)]
+    async fn list(&self) -> Result<Vec<(String, u64)>> {
+        let mut files = vec![];
+        for entry in fs::read_dir(&self.cache_dir)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            if metadata.is_file() {
+                files.push((entry.file_name().to_string_lossy().into_owned(), metadata.len()));
+            }
+        }
+        Ok(files)
+    }
+
+    /// [Cache::read]
     #[instrument(skip_all, err)]
-    pub(crate) fn read(&self, file_name: &str) -> Result<Option<Vec<u8>>> {
+    async fn read(&self, file_name: &str) -> Result<Option<Vec<u8>>> {
         let path = self.cache_dir.join(file_name);
         if !path.exists() {
             return Ok(None);
@@ -45,12 +141,177 @@ impl Cache {
         Ok(Some(fs::read(&path)?))
     }
 
-    /// Write provided data to cache file.
+    /// [Cache::remove]
     #[instrument(skip_all, err)]
-    pub(crate) fn write(&self, file_name: &str, data: &[u8]) -> Result<()> {
+    async fn remove(&self, file_name: &str) -> Result<()> {
+        fs::remove_file(self.cache_dir.join(file_name))?;
+        Ok(())
+    }
+
+    /// [Cache::write]
+    #[instrument(skip_all, err)]
+    async fn write(&self, file_name: &str, data: &[u8]) -> Result<()> {
         let path = self.cache_dir.join(file_name);
         let mut file = fs::File::create(path)?;
         file.write_all(data)?;
         Ok(())
     }
 }
+
+/// Cache implementation backed by memory. Data written to this cache is lost
+/// when the build finishes.
+#[derive(Debug, Default)]
+pub(crate) struct MemoryCache {
+    files: Mutex<HashMap<String, Vec<u8>>>,
+}
+
+#[async_trait]
+impl Cache for MemoryCache {
+    /// [Cache::list]
+    async fn list(&self) -> Result<Vec<(String, u64)>> {
+        let files = self.files.lock().expect("files lock not to be poisoned");
+        Ok(files.iter().map(|(name, data)| (name.clone(), data.len() as u64)).collect())
+    }
+
+    /// [Cache::read]
+    async fn read(&self, file_name: &str) -> Result<Option<Vec<u8>>> {
+        let files = self.files.lock().expect("files lock not to be poisoned");
+        Ok(files.get(file_name).cloned())
+    }
+
+    /// [Cache::remove]
+    async fn remove(&self, file_name: &str) -> Result<()> {
+        let mut files = self.files.lock().expect("files lock not to be poisoned");
+        files.remove(file_name);
+        Ok(())
+    }
+
+    /// [Cache::write]
+    async fn write(&self, file_name: &str, data: &[u8]) -> Result<()> {
+        let mut files = self.files.lock().expect("files lock not to be poisoned");
+        files.insert(file_name.to_string(), data.to_vec());
+        Ok(())
+    }
+}
+
+/// Cache implementation backed by AWS S3. This allows the cache to survive
+/// between builds in ephemeral environments (i.e. CI runners).
+pub(crate) struct S3Cache {
+    bucket: String,
+    path: String,
+    s3_client: aws_sdk_s3::Client,
+}
+
+impl S3Cache {
+    /// Create a new S3Cache instance. The path provided is used as the prefix
+    /// of the objects keys.
+    pub(crate) async fn new(bucket: &str, path: &str) -> Self {
+        let config = aws_config::load_from_env().await;
+        Self {
+            bucket: bucket.to_string(),
+            path: path.to_string(),
+            s3_client: aws_sdk_s3::Client::new(&config),
+        }
+    }
+}
+
+#[async_trait]
+impl Cache for S3Cache {
+    /// [Cache::list]
+    #[instrument(skip_all, err)]
+    async fn list(&self) -> Result<Vec<(String, u64)>> {
+        let prefix = format!("{}/", self.path);
+        let mut files = vec![];
+        let mut continuation_token = None;
+        loop {
+            // The delimiter prevents listing the files of other namespaces
+            let mut request =
+                self.s3_client.list_objects_v2().bucket(&self.bucket).prefix(&prefix).delimiter("/");
+            if let Some(token) = continuation_token {
+                request = request.continuation_token(token);
+            }
+            let output = request.send().await?;
+            if let Some(objects) = output.contents {
+                for object in objects {
+                    let Some(key) = object.key else { continue };
+                    let file_name = key.trim_start_matches(&prefix).to_string();
+                    files.push((file_name, u64::try_from(object.size).unwrap_or_default()));
+                }
+            }
+            if !output.is_truncated {
+                break;
+            }
+            continuation_token = output.next_continuation_token;
+        }
+        Ok(files)
+    }
+
+    /// [Cache::read]
+    #[instrument(skip_all, err)]
+    async fn read(&self, file_name: &str) -> Result<Option<Vec<u8>>> {
+        let key = format!("{}/{file_name}", self.path);
+        match self.s3_client.get_object().bucket(&self.bucket).key(key).send().await {
+            Ok(output) => Ok(Some(output.body.collect().await?.into_bytes().to_vec())),
+            Err(err) => {
+                let err = err.into_service_error();
+                if err.is_no_such_key() {
+                    return Ok(None);
+                }
+                Err(err.into())
+            }
+        }
+    }
+
+    /// [Cache::write]
+    #[instrument(skip_all, err)]
+    async fn write(&self, file_name: &str, data: &[u8]) -> Result<()> {
+        let key = format!("{}/{file_name}", self.path);
+        self.s3_client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .body(ByteStream::from(data.to_vec()))
+            .send()
+            .await?;
+        Ok(())
+    }
+
+    /// [Cache::remove]
+    #[instrument(skip_all, err)]
+    async fn remove(&self, file_name: &str) -> Result<()> {
+        let key = format!("{}/{file_name}", self.path);
+        self.s3_client.delete_object().bucket(&self.bucket).key(key).send().await?;
+        Ok(())
+    }
+}
+
+/// Cache wrapper that makes the cache provided read-only. Write and remove
+/// operations are skipped silently, so cache misses fall through to live
+/// fetches but nothing is written back (i.e. when the cache is mounted
+/// read-only from a previous CI stage).
+pub(crate) struct ReadOnlyCache {
+    cache: DynCache,
+}
+
+#[async_trait]
+impl Cache for ReadOnlyCache {
+    /// [Cache::list]
+    async fn list(&self) -> Result<Vec<(String, u64)>> {
+        self.cache.list().await
+    }
+
+    /// [Cache::read]
+    async fn read(&self, file_name: &str) -> Result<Option<Vec<u8>>> {
+        self.cache.read(file_name).await
+    }
+
+    /// [Cache::remove]
+    async fn remove(&self, _file_name: &str) -> Result<()> {
+        Ok(())
+    }
+
+    /// [Cache::write]
+    async fn write(&self, _file_name: &str, _data: &[u8]) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+//! This module provides a mechanism to limit the number of tasks that can run
+//! concurrently across all the build phases (logos preparation and data
+//! collection from external services), so that the build respects a single
+//! parallelism budget.
+
+use anyhow::{format_err, Result};
+use lazy_static::lazy_static;
+use std::sync::{Arc, RwLock};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+lazy_static! {
+    /// Semaphore shared by all the build phases (only set when a limit has
+    /// been provided).
+    static ref SEMAPHORE: RwLock<Option<Arc<Semaphore>>> = RwLock::new(None);
+}
+
+/// Set the maximum number of tasks that can run concurrently across all the
+/// build phases (no limit is applied when none is provided).
+pub(crate) fn set_max_concurrency(max_concurrency: Option<usize>) -> Result<()> {
+    if max_concurrency == Some(0) {
+        return Err(format_err!("the maximum concurrency must be greater than zero"));
+    }
+    *SEMAPHORE.write().expect("semaphore lock not to be poisoned") =
+        max_concurrency.map(|permits| Arc::new(Semaphore::new(permits)));
+    Ok(())
+}
+
+/// Acquire a permit to run a task, waiting until one is available. The permit
+/// is released when dropped. When no limit has been set, no permit is needed.
+pub(crate) async fn acquire() -> Option<OwnedSemaphorePermit> {
+    let semaphore = SEMAPHORE.read().expect("semaphore lock not to be poisoned").clone()?;
+    semaphore.acquire_owned().await.ok()
+}
@@ -0,0 +1,192 @@
+//! This module defines some types used to represent the information collected
+//! from crates.io for each of the landscape items that declare a crate name,
+//! as well as the functionality used to collect that information.
+
+use super::{
+    cache::DynCache,
+    concurrency, metrics, refresh,
+    warnings::{self, WarningKind},
+    LandscapeData,
+};
+use anyhow::{format_err, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+#[cfg(test)]
+use mockall::automock;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::Arc};
+use tracing::{debug, instrument};
+
+/// File used to cache data collected from crates.io.
+const CRATESIO_CACHE_FILE: &str = "cratesio.json";
+
+/// How long the crates.io data in the cache is valid (in days).
+const CRATESIO_CACHE_TTL: i64 = 7;
+
+/// Maximum number of concurrent requests to the crates.io API (its crawler
+/// policy asks for a low request rate).
+const CRATESIO_MAX_CONCURRENCY: usize = 1;
+
+/// Collect crates information from crates.io for each of the landscape items
+/// that declare a crate name, reusing cached data whenever possible.
+#[instrument(skip_all, err)]
+pub(crate) async fn collect_cratesio_data(
+    cache: &DynCache,
+    landscape_data: &LandscapeData,
+) -> Result<CratesIOData> {
+    // Collect names of the crates to process
+    let mut names: Vec<&String> =
+        landscape_data.items.iter().filter_map(|item| item.crate_name.as_ref()).collect();
+    names.sort();
+    names.dedup();
+    if names.is_empty() {
+        return Ok(CratesIOData::default());
+    }
+
+    debug!("collecting crates information from crates.io (this may take a while)");
+
+    // Read cached data (if available)
+    let mut cached_data: Option<CratesIOData> = None;
+    if let Ok(Some(json_data)) = cache.read(CRATESIO_CACHE_FILE).await {
+        if let Ok(cratesio_data) = serde_json::from_slice(&json_data) {
+            cached_data = Some(cratesio_data);
+        }
+    }
+
+    // Setup crates.io API client
+    let cr: DynCR = Arc::new(CRApi::new()?);
+
+    // Collect crates information, reusing cached data when available
+    let cratesio_data: CratesIOData = stream::iter(names)
+        .map(|name| async {
+            let name = name.clone();
+            if let Some(cached_crate) = cached_data.as_ref().and_then(|cache| {
+                cache.get(&name).and_then(|krate| {
+                    if refresh::is_cached_data_valid(&name, krate.generated_at, CRATESIO_CACHE_TTL) {
+                        Some(krate)
+                    } else {
+                        None
+                    }
+                })
+            }) {
+                // Use cached data when available if it hasn't expired yet
+                (name, Ok(cached_crate.clone()))
+            } else {
+                // Otherwise we pull it from the crates.io API
+                let _permit = concurrency::acquire().await;
+                let result = Crate::new(cr.clone(), &name).await;
+                metrics::record_external_data_fetch("cratesio", result.is_ok());
+                (name.clone(), result)
+            }
+        })
+        .buffer_unordered(CRATESIO_MAX_CONCURRENCY)
+        .collect::<HashMap<String, Result<Crate>>>()
+        .await
+        .into_iter()
+        .filter_map(|(name, result)| match result {
+            Ok(krate) => Some((name, krate)),
+            Err(err) => {
+                let msg = format!("error collecting crates.io data for crate {name}: {err}");
+                warnings::add(WarningKind::ExternalData, None, msg);
+                None
+            }
+        })
+        .collect();
+
+    // Write data (in json format) to cache
+    cache.write(CRATESIO_CACHE_FILE, &serde_json::to_vec_pretty(&cratesio_data)?).await?;
+
+    Ok(cratesio_data)
+}
+
+/// Type alias to represent some crates' crates.io data.
+pub(crate) type CratesIOData = HashMap<CrateName, Crate>;
+
+/// Type alias to represent a crate name.
+type CrateName = String;
+
+/// Crate information collected from crates.io.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Crate {
+    pub downloads: i64,
+    pub generated_at: DateTime<Utc>,
+    pub latest_version: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recent_downloads: Option<i64>,
+}
+
+impl Crate {
+    /// Create a new Crate instance from information obtained from crates.io.
+    async fn new(cr: DynCR, name: &str) -> Result<Self> {
+        let cr_crate = cr.get_crate(name).await?.krate;
+
+        Ok(Crate {
+            downloads: cr_crate.downloads,
+            generated_at: Utc::now(),
+            latest_version: cr_crate.max_stable_version.unwrap_or(cr_crate.newest_version),
+            recent_downloads: cr_crate.recent_downloads,
+        })
+    }
+}
+
+/// crates.io API base url.
+const CRATESIO_API_URL: &str = "https://crates.io/api/v1";
+
+/// Type alias to represent a CR trait object.
+type DynCR = Arc<dyn CR + Send + Sync>;
+
+/// Trait that defines some operations a CR implementation must support.
+#[async_trait]
+#[cfg_attr(test, automock)]
+trait CR {
+    /// Get the crate with the name provided.
+    async fn get_crate(&self, name: &str) -> Result<CRCrateResponse>;
+}
+
+/// CR implementation backed by the crates.io API.
+struct CRApi {
+    http_client: reqwest::Client,
+}
+
+impl CRApi {
+    /// Create a new CRApi instance.
+    fn new() -> Result<Self> {
+        // crates.io requires a user agent to be provided
+        let user_agent = format!("{}/{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+        let http_client = reqwest::Client::builder().user_agent(user_agent).build()?;
+
+        Ok(Self { http_client })
+    }
+}
+
+#[async_trait]
+impl CR for CRApi {
+    /// [CR::get_crate]
+    #[instrument(fields(?name), skip_all, err)]
+    async fn get_crate(&self, name: &str) -> Result<CRCrateResponse> {
+        let url = format!("{CRATESIO_API_URL}/crates/{name}");
+        let response = self.http_client.get(url).send().await?;
+        if response.status() != StatusCode::OK {
+            return Err(format_err!("unexpected status code: {:?}", response.status()));
+        }
+        let crate_response: CRCrateResponse = response.json().await?;
+        Ok(crate_response)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct CRCrateResponse {
+    #[serde(rename = "crate")]
+    krate: CRCrate,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct CRCrate {
+    downloads: i64,
+    max_stable_version: Option<String>,
+    newest_version: String,
+    recent_downloads: Option<i64>,
+}
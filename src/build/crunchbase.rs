@@ -2,7 +2,12 @@
 //! from Crunchbase for each of the landscape items (when applicable), as well
 //! as the functionality used to collect that information.
 
-use super::{cache::Cache, LandscapeData};
+use super::{
+    cache::DynCache,
+    concurrency, metrics, refresh,
+    warnings::{self, WarningKind},
+    LandscapeData,
+};
 use anyhow::{format_err, Result};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -26,18 +31,21 @@ const CRUNCHBASE_CACHE_TTL: i64 = 7;
 /// Environment variable containing the Crunchbase API key.
 const CRUNCHBASE_API_KEY: &str = "CRUNCHBASE_API_KEY";
 
+/// Permalink of the organization used to check the Crunchbase API access.
+const CRUNCHBASE_CHECK_PERMALINK: &str = "crunchbase";
+
 /// Collect Crunchbase data for each of the items orgs in the landscape,
 /// reusing cached data whenever possible.
 #[instrument(skip_all, err)]
 pub(crate) async fn collect_crunchbase_data(
-    cache: &Cache,
+    cache: &DynCache,
     landscape_data: &LandscapeData,
 ) -> Result<CrunchbaseData> {
     debug!("collecting organizations information from crunchbase (this may take a while)");
 
     // Read cached data (if available)
     let mut cached_data: Option<CrunchbaseData> = None;
-    if let Ok(Some(json_data)) = cache.read(CRUNCHBASE_CACHE_FILE) {
+    if let Ok(Some(json_data)) = cache.read(CRUNCHBASE_CACHE_FILE).await {
         if let Ok(crunchbase_data) = serde_json::from_slice(&json_data) {
             cached_data = Some(crunchbase_data);
         }
@@ -72,7 +80,7 @@ pub(crate) async fn collect_crunchbase_data(
             let url = url.clone();
             if let Some(cached_org) = cached_data.as_ref().and_then(|cache| {
                 cache.get(&url).and_then(|org| {
-                    if org.generated_at + chrono::Duration::days(CRUNCHBASE_CACHE_TTL) > Utc::now() {
+                    if refresh::is_cached_data_valid(&url, org.generated_at, CRUNCHBASE_CACHE_TTL) {
                         Some(org)
                     } else {
                         None
@@ -84,8 +92,11 @@ pub(crate) async fn collect_crunchbase_data(
             } else {
                 // Otherwise we pull it from Crunchbase if a key was provided
                 if let Some(cb) = cb.clone() {
+                    let _permit = concurrency::acquire().await;
                     limiter.acquire_one().await;
-                    (url.clone(), Organization::new(cb, &url).await)
+                    let result = Organization::new(cb, &url).await;
+                    metrics::record_external_data_fetch("crunchbase", result.is_ok());
+                    (url.clone(), result)
                 } else {
                     (url.clone(), Err(format_err!("no api key provided")))
                 }
@@ -95,24 +106,76 @@ pub(crate) async fn collect_crunchbase_data(
         .collect::<HashMap<String, Result<Organization>>>()
         .await
         .into_iter()
-        .filter_map(|(url, result)| {
-            if let Ok(crunchbase_data) = result {
-                Some((url, crunchbase_data))
-            } else {
+        .filter_map(|(url, result)| match result {
+            Ok(crunchbase_data) => Some((url, crunchbase_data)),
+            Err(err) => {
+                if cb.is_some() {
+                    let msg = format!("error collecting crunchbase data for {url}: {err}");
+                    warnings::add(WarningKind::ExternalData, None, msg);
+                }
                 None
             }
         })
         .collect();
 
     // Write data (in json format) to cache
-    cache.write(
-        CRUNCHBASE_CACHE_FILE,
-        &serde_json::to_vec_pretty(&crunchbase_data)?,
-    )?;
+    cache
+        .write(
+            CRUNCHBASE_CACHE_FILE,
+            &serde_json::to_vec_pretty(&crunchbase_data)?,
+        )
+        .await?;
 
     Ok(crunchbase_data)
 }
 
+/// Check the Crunchbase API is reachable and that the api key provided is
+/// authorized, returning whether the check was performed or not (it is
+/// skipped when no api key has been provided).
+#[instrument(skip_all, err)]
+pub(crate) async fn check_crunchbase() -> Result<bool> {
+    let api_key = match env::var(CRUNCHBASE_API_KEY) {
+        Ok(api_key) if !api_key.is_empty() => api_key,
+        Ok(_) | Err(_) => return Ok(false),
+    };
+    let cb = CBApi::new(&api_key)?;
+    cb.get_organization(CRUNCHBASE_CHECK_PERMALINK).await?;
+    Ok(true)
+}
+
+/// Check that the values of the Crunchbase data provided are plausible (i.e.
+/// no negative funding amounts or employees counts), as implausible values
+/// usually indicate a parsing bug or a bad API response. A warning is
+/// reported for each of the implausible values found.
+pub(crate) fn check_crunchbase_data(crunchbase_data: &CrunchbaseData) {
+    for (url, org) in crunchbase_data {
+        let mut issues = vec![];
+        if let Some(funding) = org.funding.filter(|funding| *funding < 0) {
+            issues.push(format!("negative funding ({funding})"));
+        }
+        for (name, value) in [
+            ("minimum employees count", org.num_employees_min),
+            ("maximum employees count", org.num_employees_max),
+        ] {
+            if let Some(value) = value.filter(|value| *value < 0) {
+                issues.push(format!("negative {name} ({value})"));
+            }
+        }
+        if let (Some(min), Some(max)) = (org.num_employees_min, org.num_employees_max) {
+            if min > max {
+                issues.push(format!(
+                    "minimum employees count ({min}) greater than the maximum ({max})"
+                ));
+            }
+        }
+        for issue in issues {
+            warn!(?url, issue, "implausible crunchbase data");
+            let msg = format!("implausible crunchbase data for {url}: {issue}");
+            warnings::add(WarningKind::ImplausibleData, None, msg);
+        }
+    }
+}
+
 /// Type alias to represent some organizations' Crunchbase data.
 pub(crate) type CrunchbaseData = HashMap<CrunchbaseUrl, Organization>;
 
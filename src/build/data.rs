@@ -8,22 +8,64 @@
 //! allows parsing the legacy format and convert it to the new one.
 
 use super::{
+    cratesio::{Crate, CratesIOData},
     crunchbase::{CrunchbaseData, Organization},
-    github::{self, GithubData},
-    settings::LandscapeSettings,
+    github::{self, GithubData, GithubOrgsData},
+    guide::markdown_to_html,
+    scorecard::{Scorecard, ScorecardData},
+    settings::{FeaturedItemRule, FeaturedItemRuleOption, LandscapeSettings},
+    social::{SocialAccount, SocialData},
+    warnings::{self, WarningKind},
 };
-use crate::DataSource;
-use anyhow::{format_err, Result};
-use chrono::NaiveDate;
-use reqwest::StatusCode;
+use crate::{DataSource, SortBy};
+use anyhow::{format_err, Context, Result};
+use chrono::{NaiveDate, Utc};
+use reqwest::{header::AUTHORIZATION, StatusCode};
+use schemars::{schema::RootSchema, schema_for, JsonSchema};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, fs, path::Path};
-use tracing::{debug, instrument};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap, HashSet},
+    env,
+    fmt::Write,
+    fs,
+    path::Path,
+};
+use tracing::{debug, instrument, warn};
 use uuid::Uuid;
 
 /// Format used for dates across the landscape data file.
 pub const DATE_FORMAT: &str = "%Y-%m-%d";
 
+/// Maximum edit distance between an unknown category name and a valid one for
+/// the latter to be suggested.
+const CATEGORY_SUGGESTION_MAX_DISTANCE: usize = 3;
+
+/// Legal suffixes removed from the organizations names when normalizing them
+/// (used when no suffixes are provided in the settings).
+const DEFAULT_ORGANIZATIONS_LEGAL_SUFFIXES: [&str; 16] = [
+    "ab",
+    "ag",
+    "bv",
+    "co",
+    "corp",
+    "corporation",
+    "gmbh",
+    "inc",
+    "incorporated",
+    "limited",
+    "llc",
+    "ltd",
+    "oy",
+    "plc",
+    "sa",
+    "srl",
+];
+
+/// Environment variable containing the value of the authorization header
+/// used when getting the landscape data file from a url (i.e. `Bearer TOKEN`).
+const DATA_URL_AUTH_HEADER: &str = "LANDSCAPE_DATA_URL_AUTH_HEADER";
+
 /// Landscape data.
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub(crate) struct LandscapeData {
@@ -50,18 +92,24 @@ impl LandscapeData {
         Err(format_err!("data file or url not provided"))
     }
 
+    /// Return the JSON Schema of the landscape data file (legacy format).
+    pub(crate) fn json_schema() -> RootSchema {
+        schema_for!(legacy::LandscapeData)
+    }
+
     /// Create a new landscape data instance from the file provided.
     fn new_from_file(file: &Path) -> Result<Self> {
         let raw_data = fs::read_to_string(file)?;
-        let legacy_data: legacy::LandscapeData = serde_yaml::from_str(&raw_data)?;
-        legacy_data.validate()?;
-
-        Ok(LandscapeData::from(legacy_data))
+        LandscapeData::new_from_yaml(&raw_data)
     }
 
     /// Create a new landscape data instance from the url provided.
     async fn new_from_url(url: &str) -> Result<Self> {
-        let resp = reqwest::get(url).await?;
+        let mut req = reqwest::Client::new().get(url);
+        if let Ok(auth_header) = env::var(DATA_URL_AUTH_HEADER) {
+            req = req.header(AUTHORIZATION, auth_header);
+        }
+        let resp = req.send().await?;
         if resp.status() != StatusCode::OK {
             return Err(format_err!(
                 "unexpected status code getting landscape data file: {}",
@@ -69,10 +117,142 @@ impl LandscapeData {
             ));
         }
         let raw_data = resp.text().await?;
-        let legacy_data: legacy::LandscapeData = serde_yaml::from_str(&raw_data)?;
+        LandscapeData::new_from_yaml(&raw_data)
+    }
+
+    /// Create a new landscape data instance from the YAML string provided
+    /// (in legacy format).
+    pub(crate) fn new_from_yaml(s: &str) -> Result<Self> {
+        let legacy_data: legacy::LandscapeData = serde_yaml::from_str(s)?;
         legacy_data.validate()?;
 
-        Ok(LandscapeData::from(legacy_data))
+        let mut landscape_data = LandscapeData::from(legacy_data);
+        landscape_data.validate_items_ids()?;
+        landscape_data.resolve_items_parents()?;
+
+        Ok(landscape_data)
+    }
+
+    /// Check that the items have the required fields and that they belong to
+    /// one of the categories and subcategories defined. The data loaded from
+    /// the landscape data file is checked when it is parsed, so this is used
+    /// to validate the data obtained from other sources (i.e. a transform
+    /// command) in the same way.
+    pub(crate) fn validate_items(&self) -> Result<()> {
+        let mut items_seen = HashSet::new();
+        for (item_index, item) in self.items.iter().enumerate() {
+            // Prepare context for errors
+            let item_id = if item.name.is_empty() {
+                format!("{item_index}")
+            } else {
+                item.name.clone()
+            };
+            let ctx = format!(
+                "item [{}] is not valid (category: [{}] | subcategory: [{}])",
+                item_id, item.category, item.subcategory
+            );
+
+            // Check required fields
+            if item.name.is_empty() {
+                return Err(format_err!("name is required")).context(ctx);
+            }
+            if !items_seen.insert(item.name.as_str()) {
+                return Err(format_err!("duplicate item name")).context(ctx);
+            }
+            if item.homepage_url.is_empty() {
+                return Err(format_err!("homepage_url is required")).context(ctx);
+            }
+            if item.logo.is_empty() {
+                return Err(format_err!("logo is required")).context(ctx);
+            }
+
+            // Check category and subcategory
+            let Some(category) = self.categories.iter().find(|c| c.name == item.category) else {
+                return Err(format_err!("category not found")).context(ctx);
+            };
+            if !category.subcategories.contains(&item.subcategory) {
+                return Err(format_err!("subcategory not found")).context(ctx);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that the items ids are unique. Ids are generated from the item's
+    /// category, subcategory and name, so items with the same name in the
+    /// same subcategory would collide (ids are well-formed UUIDs by type).
+    pub(crate) fn validate_items_ids(&self) -> Result<()> {
+        let mut items_by_id: HashMap<Uuid, &Item> = HashMap::new();
+        let mut duplicates = String::new();
+        for item in &self.items {
+            if let Some(other) = items_by_id.insert(item.id, item) {
+                _ = writeln!(duplicates, "- {}: {} and {}", item.id, other.name, item.name);
+            }
+        }
+        if !duplicates.is_empty() {
+            return Err(format_err!(
+                "duplicated items ids found:\n{}",
+                duplicates.trim_end()
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Resolve the items parents references (the id or the name of another
+    /// item) to the parent items ids, checking that they point to an existing
+    /// item and that they don't introduce any cycles.
+    #[instrument(skip_all, err)]
+    pub(crate) fn resolve_items_parents(&mut self) -> Result<()> {
+        // Resolve references
+        let ids: HashSet<String> = self.items.iter().map(|item| item.id.to_string()).collect();
+        let mut ids_by_name: HashMap<&str, Vec<Uuid>> = HashMap::new();
+        for item in &self.items {
+            ids_by_name.entry(item.name.as_str()).or_default().push(item.id);
+        }
+        let mut parents = vec![];
+        for item in &self.items {
+            let parent = match &item.parent {
+                None => None,
+                Some(parent) if ids.contains(parent) => Some(parent.clone()),
+                Some(parent) => match ids_by_name.get(parent.as_str()).map(Vec::as_slice) {
+                    Some([id]) => Some(id.to_string()),
+                    Some(_) => {
+                        return Err(format_err!(
+                            "item {} parent {parent} is ambiguous (multiple items with that name found, please use its id)",
+                            item.name
+                        ))
+                    }
+                    None => return Err(format_err!("item {} parent {parent} not found", item.name)),
+                },
+            };
+            parents.push(parent);
+        }
+        for (item, parent) in self.items.iter_mut().zip(parents) {
+            item.parent = parent;
+        }
+
+        // Check there are no cycles
+        let parents_by_id: HashMap<String, &String> = self
+            .items
+            .iter()
+            .filter_map(|item| item.parent.as_ref().map(|parent| (item.id.to_string(), parent)))
+            .collect();
+        for item in &self.items {
+            let mut visited = HashSet::from([item.id.to_string()]);
+            let mut current = item.id.to_string();
+            while let Some(parent) = parents_by_id.get(&current) {
+                if !visited.insert((*parent).clone()) {
+                    return Err(format_err!(
+                        "item {} parents relationships contain a cycle",
+                        item.name
+                    ));
+                }
+                current = (*parent).clone();
+            }
+        }
+
+        Ok(())
     }
 
     /// Add items Crunchbase data.
@@ -98,15 +278,14 @@ impl LandscapeData {
         };
 
         for rule in rules {
+            validate_featured_items_rule_weights(rule)?;
+
             match rule.field.as_str() {
                 "maturity" => {
                     for item in &mut self.items {
                         if let Some(item_maturity) = item.maturity.as_ref() {
                             if let Some(option) = rule.options.iter().find(|o| o.value == *item_maturity) {
-                                item.featured = Some(ItemFeatured {
-                                    order: option.order,
-                                    label: option.label.clone(),
-                                });
+                                item.featured = Some(ItemFeatured::from(option));
                             }
                         }
                     }
@@ -114,10 +293,7 @@ impl LandscapeData {
                 "subcategory" => {
                     for item in &mut self.items {
                         if let Some(option) = rule.options.iter().find(|o| o.value == item.subcategory) {
-                            item.featured = Some(ItemFeatured {
-                                order: option.order,
-                                label: option.label.clone(),
-                            });
+                            item.featured = Some(ItemFeatured::from(option));
                         }
                     }
                 }
@@ -128,6 +304,17 @@ impl LandscapeData {
         Ok(())
     }
 
+    /// Add data collected from crates.io for the items crates.
+    #[instrument(skip_all, err)]
+    pub(crate) fn add_cratesio_data(&mut self, cratesio_data: CratesIOData) -> Result<()> {
+        for item in &mut self.items {
+            if let Some(crate_name) = &item.crate_name {
+                item.cratesio_data = cratesio_data.get(crate_name).cloned();
+            }
+        }
+        Ok(())
+    }
+
     /// Add items repositories GitHub data.
     #[instrument(skip_all, err)]
     pub(crate) fn add_github_data(&mut self, github_data: GithubData) -> Result<()> {
@@ -157,6 +344,195 @@ impl LandscapeData {
         Ok(())
     }
 
+    /// Add data collected from GitHub for the organizations owning the items'
+    /// primary repositories (only repositories hosted on the GitHub host
+    /// provided are considered).
+    #[instrument(skip_all, err)]
+    pub(crate) fn add_github_orgs_data(
+        &mut self,
+        github_orgs_data: GithubOrgsData,
+        host: &str,
+    ) -> Result<()> {
+        for item in &mut self.items {
+            if let Some(repo) = item.primary_repository() {
+                let Ok((owner, _)) = github::get_owner_and_repo(&repo.url, host) else {
+                    continue;
+                };
+                if let Some(org_github_data) = github_orgs_data.get(&owner) {
+                    item.github_org_data = Some(org_github_data.clone());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the items whose primary repository has fewer GitHub stars than
+    /// the minimum provided, along with their stars count. Items without
+    /// GitHub data are not considered.
+    pub(crate) fn items_below_min_stars(&self, min_stars: i64) -> Vec<(&Item, i64)> {
+        self.items
+            .iter()
+            .filter_map(|item| {
+                let stars = item.primary_repository()?.github_data.as_ref()?.stars;
+                (stars < min_stars).then_some((item, stars))
+            })
+            .collect()
+    }
+
+    /// Add items repositories OpenSSF Scorecard data.
+    #[instrument(skip_all, err)]
+    pub(crate) fn add_scorecard_data(&mut self, scorecard_data: ScorecardData) -> Result<()> {
+        for item in &mut self.items {
+            if let Some(repositories) = &mut item.repositories {
+                for repo in repositories {
+                    if let Some(repo_scorecard) = scorecard_data.get(&repo.url) {
+                        repo.scorecard = Some(repo_scorecard.clone());
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Add data collected from social networks for the items accounts.
+    #[instrument(skip_all, err)]
+    pub(crate) fn add_social_data(&mut self, social_data: SocialData) -> Result<()> {
+        for item in &mut self.items {
+            if let Some(url) = &item.bluesky_url {
+                item.bluesky_data = social_data.get(url).cloned();
+            }
+            if let Some(url) = &item.mastodon_url {
+                item.mastodon_data = social_data.get(url).cloned();
+            }
+        }
+        Ok(())
+    }
+
+    /// Sort the landscape items using the key provided. Ties are resolved by
+    /// name (and id), so that the resulting order is deterministic.
+    pub(crate) fn sort_items(&mut self, sort_by: SortBy) {
+        let stars = |item: &Item| {
+            item.primary_repository()
+                .and_then(|repo| repo.github_data.as_ref())
+                .map(|gh_data| gh_data.stars)
+        };
+        let added_at = |item: &Item| item.accepted_at.or(item.joined_at);
+
+        self.items.sort_by(|a, b| {
+            let ordering = match sort_by {
+                // Most recently added items first
+                SortBy::AddedAt => added_at(b).cmp(&added_at(a)),
+                SortBy::Category => (&a.category, &a.subcategory).cmp(&(&b.category, &b.subcategory)),
+                SortBy::Name => Ordering::Equal,
+                // Items with more stars first
+                SortBy::Stars => stars(b).cmp(&stars(a)),
+            };
+            ordering
+                .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+                .then_with(|| a.id.cmp(&b.id))
+        });
+    }
+
+    /// Check that the categories referenced by the items are defined in the
+    /// settings provided. As the categories defined in the settings are only
+    /// used to override some of them, only the categories that look like a
+    /// misspelling of one of them (i.e. "Databse" instead of "Database") are
+    /// reported, suggesting the closest valid category name.
+    #[instrument(skip_all)]
+    pub(crate) fn validate_items_categories(&self, settings: &LandscapeSettings) {
+        let Some(settings_categories) = &settings.categories else {
+            return;
+        };
+
+        let mut items_categories: Vec<&str> = self.items.iter().map(|item| item.category.as_str()).collect();
+        items_categories.sort_unstable();
+        items_categories.dedup();
+        for name in items_categories {
+            if settings_categories.iter().any(|c| c.name == name) {
+                continue;
+            }
+            if let Some(suggestion) = closest_category_name(settings_categories, name) {
+                let msg = format!(
+                    "category '{name}' referenced by items not found in settings categories (did you mean '{suggestion}'?)"
+                );
+                warnings::add(WarningKind::Settings, None, msg);
+            }
+        }
+    }
+
+    /// Check that the tags referenced by the items are defined in the settings
+    /// provided.
+    #[instrument(skip_all, err)]
+    pub(crate) fn validate_items_tags(&self, settings: &LandscapeSettings) -> Result<()> {
+        let tags = settings.tags.as_deref().unwrap_or_default();
+        for item in &self.items {
+            for tag in item.tags.as_deref().unwrap_or_default() {
+                if !tags.iter().any(|t| &t.name == tag) {
+                    return Err(format_err!(
+                        "tag '{tag}' referenced by item {} not defined in settings tags",
+                        item.name
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Remove the items that have reached their end of life date.
+    pub(crate) fn remove_eol_items(&mut self) {
+        self.items.retain(|item| {
+            let eol = item.is_eol();
+            if eol {
+                debug!(?item.name, "removing end of life item");
+            }
+            !eol
+        });
+
+        // Remove references to parents that are no longer available
+        let ids: HashSet<String> = self.items.iter().map(|item| item.id.to_string()).collect();
+        for item in &mut self.items {
+            if item.parent.as_ref().is_some_and(|parent| !ids.contains(parent)) {
+                item.parent = None;
+            }
+        }
+    }
+
+    /// Render the items descriptions (markdown) to sanitized HTML, keeping the
+    /// raw descriptions as well.
+    #[instrument(skip_all, err)]
+    pub(crate) fn render_items_descriptions(&mut self, sanitizer: &ammonia::Builder<'_>) -> Result<()> {
+        for item in &mut self.items {
+            if let Some(description) = &item.description {
+                item.description_html = Some(markdown_to_html(description, sanitizer)?);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that the maturity of the items is one of the maturity levels
+    /// defined in the settings provided (when defined).
+    #[instrument(skip_all, err)]
+    pub(crate) fn validate_items_maturity(&self, settings: &LandscapeSettings) -> Result<()> {
+        let Some(maturity_levels) = &settings.maturity_levels else {
+            return Ok(());
+        };
+        for item in &self.items {
+            if let Some(maturity) = &item.maturity {
+                if !maturity_levels.contains(maturity) {
+                    return Err(format_err!(
+                        "maturity '{maturity}' of item {} not defined in settings maturity levels (allowed: {})",
+                        item.name,
+                        maturity_levels.join(", ")
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Add items member subcategory.
     #[instrument(skip_all)]
     pub(crate) fn add_member_subcategory(&mut self, members_category: &Option<String>) {
@@ -181,6 +557,74 @@ impl LandscapeData {
             }
         }
     }
+
+    /// Normalize the names of the items organizations (Crunchbase data), so
+    /// that different spellings of the same organization (i.e. "Foo, Inc." and
+    /// "Foo Inc") collapse into a single one. Names are compared ignoring the
+    /// case, the punctuation and the legal suffixes provided (a default list is
+    /// used when none are provided). All the items of the same organization
+    /// will use its most common spelling.
+    #[instrument(skip_all)]
+    pub(crate) fn normalize_organizations_names(&mut self, legal_suffixes: &Option<Vec<String>>) {
+        let legal_suffixes: Vec<String> = match legal_suffixes {
+            Some(legal_suffixes) => legal_suffixes.iter().map(|s| normalize_name(s, &[])).collect(),
+            None => DEFAULT_ORGANIZATIONS_LEGAL_SUFFIXES.iter().map(ToString::to_string).collect(),
+        };
+
+        // Count the spellings used for each of the organizations
+        let mut spellings: HashMap<String, BTreeMap<String, usize>> = HashMap::new();
+        for item in &self.items {
+            if let Some(name) = item.crunchbase_data.as_ref().and_then(|org| org.name.as_ref()) {
+                let count = spellings
+                    .entry(normalize_name(name, &legal_suffixes))
+                    .or_default()
+                    .entry(name.clone())
+                    .or_default();
+                *count += 1;
+            }
+        }
+
+        // Use the most common spelling of each organization (ties are
+        // resolved alphabetically so that the result is stable)
+        for item in &mut self.items {
+            if let Some(name) = item.crunchbase_data.as_mut().and_then(|org| org.name.as_mut()) {
+                if let Some(canonical_name) = spellings
+                    .get(&normalize_name(name, &legal_suffixes))
+                    .and_then(|s| s.iter().max_by(|(n1, c1), (n2, c2)| c1.cmp(c2).then(n2.cmp(n1))))
+                    .map(|(name, _)| name)
+                {
+                    name.clone_from(canonical_name);
+                }
+            }
+        }
+    }
+}
+
+/// Return the name of the category closest to the one provided, as long as it
+/// is within the maximum edit distance allowed.
+fn closest_category_name<'a>(categories: &'a [Category], name: &str) -> Option<&'a str> {
+    categories
+        .iter()
+        .map(|c| {
+            (
+                strsim::levenshtein(&name.to_lowercase(), &c.name.to_lowercase()),
+                &c.name,
+            )
+        })
+        .filter(|(distance, _)| *distance <= CATEGORY_SUGGESTION_MAX_DISTANCE)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, name)| name.as_str())
+}
+
+/// Normalize the name provided, lowercasing it, removing the punctuation and
+/// the trailing legal suffixes provided (suffixes must be normalized).
+fn normalize_name(name: &str, legal_suffixes: &[String]) -> String {
+    let name = name.to_lowercase().replace(|c: char| !c.is_alphanumeric() && !c.is_whitespace(), " ");
+    let mut words: Vec<&str> = name.split_whitespace().collect();
+    while words.len() > 1 && words.last().is_some_and(|w| legal_suffixes.iter().any(|s| s == w)) {
+        words.pop();
+    }
+    words.join(" ")
 }
 
 impl From<legacy::LandscapeData> for LandscapeData {
@@ -211,9 +655,12 @@ impl From<legacy::LandscapeData> for LandscapeData {
                         joined_at: legacy_item.joined,
                         homepage_url: legacy_item.homepage_url,
                         logo: legacy_item.logo,
+                        logo_mirrors: legacy_item.logo_mirrors,
                         maturity: legacy_item.project,
+                        metadata: legacy_item.metadata,
                         openssf_best_practices_url: legacy_item.url_for_bestpractices,
                         subcategory: legacy_subcategory.name.clone(),
+                        tags: legacy_item.tags,
                         twitter_url: legacy_item.twitter,
                         unnamed_organization: legacy_item.unnamed_organization,
                         ..Default::default()
@@ -251,16 +698,22 @@ impl From<legacy::LandscapeData> for LandscapeData {
                         item.artwork_url = extra.artwork_url;
                         item.audits = extra.audits;
                         item.blog_url = extra.blog_url;
+                        item.bluesky_url = extra.bluesky_url;
                         item.chat_channel = extra.chat_channel;
                         item.clomonitor_name = extra.clomonitor_name;
+                        item.crate_name = extra.crate_name;
                         item.devstats_url = extra.dev_stats_url;
                         item.discord_url = extra.discord_url;
+                        item.eol_at = extra.eol_date;
+                        item.feed_url = extra.feed_url;
                         item.github_discussions_url = extra.github_discussions_url;
                         item.graduated_at = extra.graduated;
                         item.incubating_at = extra.incubating;
                         item.latest_annual_review_at = extra.annual_review_date;
                         item.latest_annual_review_url = extra.annual_review_url;
                         item.mailing_list_url = extra.mailing_list_url;
+                        item.mastodon_url = extra.mastodon_url;
+                        item.parent = extra.parent;
                         item.slack_url = extra.slack_url;
                         item.specification = extra.specification;
                         item.stack_overflow_url = extra.stack_overflow_url;
@@ -305,7 +758,7 @@ impl From<legacy::LandscapeData> for LandscapeData {
 }
 
 /// Landscape category.
-#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub(crate) struct Category {
     pub name: CategoryName,
     pub subcategories: Vec<SubCategoryName>,
@@ -342,12 +795,24 @@ pub(crate) struct Item {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub blog_url: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bluesky_data: Option<SocialAccount>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bluesky_url: Option<String>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub chat_channel: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub clomonitor_name: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crate_name: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cratesio_data: Option<Crate>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub crunchbase_data: Option<Organization>,
 
@@ -357,6 +822,9 @@ pub(crate) struct Item {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description_html: Option<String>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub devstats_url: Option<String>,
 
@@ -369,12 +837,21 @@ pub(crate) struct Item {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub enduser: Option<bool>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eol_at: Option<NaiveDate>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub featured: Option<ItemFeatured>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feed_url: Option<String>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub github_discussions_url: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub github_org_data: Option<github::GithubOrganization>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub graduated_at: Option<NaiveDate>,
 
@@ -384,15 +861,30 @@ pub(crate) struct Item {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub joined_at: Option<NaiveDate>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logo_dominant_color: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logo_mirrors: Option<Vec<String>>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mailing_list_url: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mastodon_data: Option<SocialAccount>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mastodon_url: Option<String>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub maturity: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub member_subcategory: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ItemMetadata>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub latest_annual_review_at: Option<NaiveDate>,
 
@@ -405,6 +897,12 @@ pub(crate) struct Item {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub oss: Option<bool>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qr_code: Option<String>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub repositories: Option<Vec<Repository>>,
 
@@ -420,6 +918,9 @@ pub(crate) struct Item {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub summary: Option<ItemSummary>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub twitter_url: Option<String>,
 
@@ -451,6 +952,11 @@ impl Item {
         description
     }
 
+    /// Check if the item has reached its end of life date.
+    pub(crate) fn is_eol(&self) -> bool {
+        self.eol_at.is_some_and(|eol_at| eol_at <= Utc::now().date_naive())
+    }
+
     /// Get primary repository if available.
     #[allow(dead_code)]
     pub(crate) fn primary_repository(&self) -> Option<&Repository> {
@@ -466,8 +972,12 @@ impl Item {
     }
 }
 
+/// Landscape item custom metadata. It can contain any fields, which are not
+/// processed in any way and are included as is in the datasets.
+pub(crate) type ItemMetadata = BTreeMap<String, serde_json::Value>;
+
 /// Landscape item audit information.
-#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub(crate) struct ItemAudit {
     pub date: NaiveDate,
     #[serde(rename = "type")]
@@ -484,6 +994,51 @@ pub(crate) struct ItemFeatured {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub order: Option<usize>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+}
+
+impl From<&FeaturedItemRuleOption> for ItemFeatured {
+    fn from(option: &FeaturedItemRuleOption) -> Self {
+        Self {
+            label: option.label.clone(),
+            order: option.order,
+            weight: option.weight,
+        }
+    }
+}
+
+/// Check that the weights of the featured items rule options provided are
+/// finite numbers. Duplicated weights are allowed, but a warning is emitted as
+/// the relative order of the items affected will not be deterministic.
+fn validate_featured_items_rule_weights(rule: &FeaturedItemRule) -> Result<()> {
+    let mut weights_seen: Vec<f64> = vec![];
+    for option in &rule.options {
+        let Some(weight) = option.weight else {
+            continue;
+        };
+        if !weight.is_finite() {
+            return Err(format_err!(
+                "invalid weight in featured items rule ({}) option {}: must be a finite number",
+                rule.field,
+                option.value
+            ));
+        }
+        if weights_seen.contains(&weight) {
+            warnings::add(
+                WarningKind::Settings,
+                None,
+                format!(
+                    "duplicated weight ({weight}) in featured items rule ({}) option {}",
+                    rule.field, option.value
+                ),
+            );
+        }
+        weights_seen.push(weight);
+    }
+
+    Ok(())
 }
 
 /// Landscape item summary.
@@ -527,21 +1082,28 @@ pub(crate) struct Repository {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub primary: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scorecard: Option<Scorecard>,
 }
 
 mod legacy {
     //! This module defines some types used to parse the landscape data file in
     //! legacy format and convert it to the new one.
 
-    use super::ItemAudit;
-    use crate::build::crunchbase::CRUNCHBASE_URL;
+    use super::{ItemAudit, ItemMetadata};
+    use crate::build::{
+        crunchbase::CRUNCHBASE_URL,
+        social::{BLUESKY_URL, MASTODON_URL},
+    };
     use anyhow::{format_err, Context, Result};
     use chrono::NaiveDate;
+    use schemars::JsonSchema;
     use serde::{Deserialize, Serialize};
     use url::Url;
 
     /// Landscape data (legacy format).
-    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
     pub(crate) struct LandscapeData {
         pub landscape: Vec<Category>,
     }
@@ -608,21 +1170,21 @@ mod legacy {
     }
 
     /// Landscape category.
-    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
     pub(crate) struct Category {
         pub name: String,
         pub subcategories: Vec<SubCategory>,
     }
 
     /// Landscape subcategory.
-    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
     pub(crate) struct SubCategory {
         pub name: String,
         pub items: Vec<Item>,
     }
 
     /// Landscape item (project, product, member, etc).
-    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
     pub(crate) struct Item {
         pub name: String,
         pub homepage_url: String,
@@ -634,22 +1196,25 @@ mod legacy {
         pub enduser: Option<bool>,
         pub extra: Option<ItemExtra>,
         pub joined: Option<NaiveDate>,
+        pub logo_mirrors: Option<Vec<String>>,
+        pub metadata: Option<ItemMetadata>,
         pub project: Option<String>,
         pub repo_url: Option<String>,
+        pub tags: Option<Vec<String>>,
         pub twitter: Option<String>,
         pub url_for_bestpractices: Option<String>,
         pub unnamed_organization: Option<bool>,
     }
 
     /// Landscape item repository.
-    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
     pub(crate) struct Repository {
         pub repo_url: String,
         pub branch: Option<String>,
     }
 
     /// Extra information for a landscape item.
-    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
     pub(crate) struct ItemExtra {
         pub accepted: Option<NaiveDate>,
         pub archived: Option<NaiveDate>,
@@ -658,15 +1223,21 @@ mod legacy {
         pub annual_review_url: Option<String>,
         pub artwork_url: Option<String>,
         pub blog_url: Option<String>,
+        pub bluesky_url: Option<String>,
         pub chat_channel: Option<String>,
         pub clomonitor_name: Option<String>,
+        pub crate_name: Option<String>,
         pub dev_stats_url: Option<String>,
         pub discord_url: Option<String>,
         pub docker_url: Option<String>,
+        pub eol_date: Option<NaiveDate>,
+        pub feed_url: Option<String>,
         pub github_discussions_url: Option<String>,
         pub graduated: Option<NaiveDate>,
         pub incubating: Option<NaiveDate>,
         pub mailing_list_url: Option<String>,
+        pub mastodon_url: Option<String>,
+        pub parent: Option<String>,
         pub slack_url: Option<String>,
         pub specification: Option<bool>,
         pub stack_overflow_url: Option<String>,
@@ -696,6 +1267,13 @@ mod legacy {
             validate_url(name, url)?;
         }
 
+        // Check logo mirrors
+        if let Some(logo_mirrors) = &item.logo_mirrors {
+            for url in logo_mirrors {
+                validate_url("logo_mirror", &Some(url.clone()))?;
+            }
+        }
+
         // Check additional repositories
         if let Some(additional_repos) = &item.additional_repos {
             for r in additional_repos {
@@ -710,11 +1288,14 @@ mod legacy {
                 ("annual_review", &extra.annual_review_url),
                 ("artwork", &extra.artwork_url),
                 ("blog", &extra.blog_url),
+                ("bluesky", &extra.bluesky_url),
                 ("dev_stats", &extra.dev_stats_url),
                 ("discord", &extra.discord_url),
                 ("docker", &extra.docker_url),
+                ("feed", &extra.feed_url),
                 ("github_discussions", &extra.github_discussions_url),
                 ("mailing_list", &extra.mailing_list_url),
+                ("mastodon", &extra.mastodon_url),
                 ("slack", &extra.slack_url),
                 ("stack_overflow", &extra.stack_overflow_url),
                 ("youtube", &extra.youtube_url),
@@ -753,11 +1334,17 @@ mod legacy {
 
             // Some checks specific to the url kind provided
             match kind {
+                "bluesky" if !BLUESKY_URL.is_match(url.as_str()) => {
+                    return invalid_url(&format!("expecting: {}", BLUESKY_URL.as_str()));
+                }
                 "crunchbase" => {
                     if !CRUNCHBASE_URL.is_match(url.as_str()) {
                         return invalid_url(&format!("expecting: {}", CRUNCHBASE_URL.as_str()));
                     }
                 }
+                "mastodon" if !MASTODON_URL.is_match(url.as_str()) => {
+                    return invalid_url(&format!("expecting: {}", MASTODON_URL.as_str()));
+                }
                 "stack_overflow" => {
                     if url.host_str().is_some_and(|host| !host.contains("stackoverflow.com")) {
                         return invalid_url("invalid stack overflow url");
@@ -7,7 +7,9 @@
 
 use self::{base::Base, full::Full};
 use super::{settings::LandscapeSettings, LandscapeData};
-use anyhow::{Ok, Result};
+use anyhow::{Context, Ok, Result};
+use serde::{de::DeserializeOwned, Serialize};
+use url::Url;
 
 /// Datasets collection.
 #[derive(Debug, Clone)]
@@ -26,13 +28,101 @@ impl Datasets {
         settings: &LandscapeSettings,
         includes_guide: bool,
     ) -> Result<Self> {
-        let datasets = Datasets {
-            base: Base::new(landscape_data, settings, includes_guide),
+        let mut datasets = Datasets {
+            base: Base::new(landscape_data, settings, includes_guide)?,
             full: Full::new(landscape_data.clone()),
         };
 
+        // Remove the fields that should not be published
+        if let Some(fields) = &settings.redacted_fields {
+            for item in &mut datasets.base.items {
+                *item = redact_fields(item, fields)?;
+            }
+            for item in &mut datasets.full.items {
+                *item = redact_fields(item, fields)?;
+            }
+        }
+
         Ok(datasets)
     }
+
+    /// Rewrite the references to the local logos and images in the datasets
+    /// to absolute urls under the assets base url provided (i.e. when they
+    /// are served from a CDN). The base url is also added to the base dataset,
+    /// so that the web application can fetch the other datasets from it.
+    pub(crate) fn rewrite_assets_urls(&mut self, assets_base_url: &str) {
+        let assets_base_url = assets_base_url.trim_end_matches('/');
+        let rewrite = |path: &mut String| {
+            if !path.is_empty() && Url::parse(path).is_err() {
+                *path = format!("{assets_base_url}/{}", path.trim_start_matches("./"));
+            }
+        };
+        let rewrite_srcset = |srcset: &mut String| {
+            let entries: Vec<String> = srcset
+                .split(',')
+                .map(|entry| {
+                    let mut parts = entry.trim().splitn(2, ' ');
+                    let mut path = parts.next().unwrap_or_default().to_string();
+                    rewrite(&mut path);
+                    match parts.next() {
+                        Some(descriptor) => format!("{path} {descriptor}"),
+                        None => path,
+                    }
+                })
+                .collect();
+            *srcset = entries.join(", ");
+        };
+
+        // Settings images
+        let images = &mut self.base.images;
+        for image in [
+            &mut images.favicon,
+            &mut images.footer_logo,
+            &mut images.header_logo,
+            &mut images.open_graph,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            rewrite(image);
+        }
+        for srcset in [&mut images.footer_logo_srcset, &mut images.header_logo_srcset].into_iter().flatten() {
+            rewrite_srcset(srcset);
+        }
+
+        // Items logos and QR codes
+        for item in &mut self.base.items {
+            rewrite(&mut item.logo);
+        }
+        for item in &mut self.full.items {
+            rewrite(&mut item.logo);
+            if let Some(qr_code) = &mut item.qr_code {
+                rewrite(qr_code);
+            }
+        }
+
+        self.base.assets_base_url = Some(assets_base_url.to_string());
+    }
+}
+
+/// Remove the fields provided from the value. Nested fields can be referenced
+/// using a dot separated path (i.e. `crunchbase_data.funding`).
+fn redact_fields<T: Serialize + DeserializeOwned>(value: &T, fields: &[String]) -> Result<T> {
+    let mut json_value = serde_json::to_value(value)?;
+    for field in fields {
+        let mut path: Vec<&str> = field.split('.').collect();
+        let Some(key) = path.pop() else {
+            continue;
+        };
+        let mut parent = Some(&mut json_value);
+        for segment in path {
+            parent = parent.and_then(|p| p.get_mut(segment));
+        }
+        if let Some(serde_json::Value::Object(parent)) = parent {
+            parent.remove(key);
+        }
+    }
+    serde_json::from_value(json_value).context("invalid redacted field (required fields cannot be redacted)")
 }
 
 /// Base dataset.
@@ -42,11 +132,24 @@ impl Datasets {
 mod base {
     use crate::build::{
         data::{Category, CategoryName, ItemFeatured, LandscapeData},
-        settings::{Colors, GridItemsSize, Group, Images, LandscapeSettings, SocialNetworks},
+        settings::{Colors, GridItemsSize, Group, Images, LandscapeSettings, SocialNetworks, Tag},
     };
+    use anyhow::{format_err, Result};
+    use chrono::{DateTime, Utc};
     use serde::{Deserialize, Serialize};
     use uuid::Uuid;
 
+    /// Items card fields that can be added to the base dataset (they are taken
+    /// from the item's primary repository and are only included when listed
+    /// in the base items fields allowlist).
+    const ITEMS_CARD_FIELDS: [&str; 4] = ["language", "latest_commit", "license", "stars"];
+
+    /// Optional items fields that can be included in the base dataset. When
+    /// the settings provide an allowlist, only the fields listed on it will be
+    /// included (the required fields are always included).
+    const ITEMS_OPTIONAL_FIELDS: [&str; 7] =
+        ["eol", "featured", "maturity", "oss", "parent", "tags", "topics"];
+
     /// Base dataset information.
     #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
     pub(crate) struct Base {
@@ -54,6 +157,9 @@ mod base {
         pub images: Images,
         pub includes_guide: bool,
 
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub assets_base_url: Option<String>,
+
         #[serde(skip_serializing_if = "Vec::is_empty")]
         pub categories: Vec<Category>,
 
@@ -72,8 +178,14 @@ mod base {
         #[serde(skip_serializing_if = "Vec::is_empty")]
         pub items: Vec<Item>,
 
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        pub maturity_levels: Vec<String>,
+
         #[serde(skip_serializing_if = "Option::is_none")]
         pub social_networks: Option<SocialNetworks>,
+
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        pub tags: Vec<Tag>,
     }
 
     /// Base dataset item information.
@@ -85,14 +197,38 @@ mod base {
         pub logo: String,
         pub subcategory: String,
 
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub eol: Option<bool>,
+
         #[serde(skip_serializing_if = "Option::is_none")]
         pub featured: Option<ItemFeatured>,
 
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub language: Option<String>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub latest_commit: Option<DateTime<Utc>>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub license: Option<String>,
+
         #[serde(skip_serializing_if = "Option::is_none")]
         pub maturity: Option<String>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
         pub oss: Option<bool>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub parent: Option<String>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub stars: Option<i64>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub tags: Option<Vec<String>>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub topics: Option<Vec<String>>,
     }
 
     impl Base {
@@ -101,7 +237,7 @@ mod base {
             landscape_data: &LandscapeData,
             settings: &LandscapeSettings,
             includes_guide: bool,
-        ) -> Self {
+        ) -> Result<Self> {
             let mut base = Base {
                 foundation: settings.foundation.clone(),
                 images: settings.images.clone(),
@@ -110,7 +246,9 @@ mod base {
                 colors: settings.colors.clone(),
                 grid_items_size: settings.grid_items_size.clone(),
                 groups: settings.groups.clone().unwrap_or(vec![]),
+                maturity_levels: settings.maturity_levels.clone().unwrap_or(vec![]),
                 social_networks: settings.social_networks.clone(),
+                tags: settings.tags.clone().unwrap_or(vec![]),
                 ..Default::default()
             };
 
@@ -128,6 +266,7 @@ mod base {
             for item in &landscape_data.items {
                 base.items.push(Item {
                     category: item.category.clone(),
+                    eol: item.is_eol().then_some(true),
                     featured: item.featured.clone(),
                     id: item.id,
                     name: item.name.clone(),
@@ -135,10 +274,73 @@ mod base {
                     maturity: item.maturity.clone(),
                     subcategory: item.subcategory.clone(),
                     oss: item.oss,
+                    parent: item.parent.clone(),
+                    tags: item.tags.clone(),
+                    topics: item
+                        .primary_repository()
+                        .and_then(|r| r.github_data.as_ref())
+                        .and_then(|gh_data| gh_data.topics.clone()),
+                    ..Default::default()
                 });
             }
 
-            base
+            // Remove items optional fields not included in the allowlist, and
+            // add the card fields listed on it
+            if let Some(fields) = &settings.base_items_fields {
+                if let Some(field) = fields.iter().find(|f| {
+                    !ITEMS_OPTIONAL_FIELDS.contains(&f.as_str()) && !ITEMS_CARD_FIELDS.contains(&f.as_str())
+                }) {
+                    return Err(format_err!(
+                        "invalid base items field: {field} (expecting one of: {}, {})",
+                        ITEMS_OPTIONAL_FIELDS.join(", "),
+                        ITEMS_CARD_FIELDS.join(", ")
+                    ));
+                }
+                let included = |field: &str| fields.iter().any(|f| f == field);
+                let excluded = |field: &str| !included(field);
+                for (item, landscape_item) in base.items.iter_mut().zip(&landscape_data.items) {
+                    if let Some(gh_data) =
+                        landscape_item.primary_repository().and_then(|r| r.github_data.as_ref())
+                    {
+                        if included("language") {
+                            item.language.clone_from(&gh_data.primary_language);
+                        }
+                        if included("latest_commit") {
+                            item.latest_commit = gh_data.latest_commit.ts;
+                        }
+                        if included("license") {
+                            item.license.clone_from(&gh_data.license);
+                        }
+                        if included("stars") {
+                            item.stars = Some(gh_data.stars);
+                        }
+                    }
+
+                    if excluded("eol") {
+                        item.eol = None;
+                    }
+                    if excluded("featured") {
+                        item.featured = None;
+                    }
+                    if excluded("maturity") {
+                        item.maturity = None;
+                    }
+                    if excluded("oss") {
+                        item.oss = None;
+                    }
+                    if excluded("parent") {
+                        item.parent = None;
+                    }
+                    if excluded("tags") {
+                        item.tags = None;
+                    }
+                    if excluded("topics") {
+                        item.topics = None;
+                    }
+                }
+            }
+
+            Ok(base)
         }
     }
 }
@@ -164,5 +366,28 @@ mod full {
         pub(crate) fn new(data: LandscapeData) -> Self {
             Full { items: data.items }
         }
+
+        /// Create a new Full instance containing only the items that belong
+        /// to the category provided.
+        pub(crate) fn for_category(&self, category: &str) -> Self {
+            Full {
+                items: self.items.iter().filter(|i| i.category == category).cloned().collect(),
+            }
+        }
+
+        /// Split the items into the number of shards provided (the last one
+        /// may contain fewer items). The items order is preserved.
+        pub(crate) fn shards(&self, count: usize) -> Vec<Self> {
+            let shard_size = ((self.items.len() + count - 1) / count).max(1);
+            let mut shards: Vec<Self> = self
+                .items
+                .chunks(shard_size)
+                .map(|items| Full {
+                    items: items.to_vec(),
+                })
+                .collect();
+            shards.resize_with(count, Full::default);
+            shards
+        }
     }
 }
@@ -1,12 +1,12 @@
-//! This module defines the functionality to generate the `items.csv`
-//! file from the information available in the landscape.
+//! This module defines the functionality to generate the `items.csv` and the
+//! landscape graph files from the information available in the landscape.
 
 use super::{data, LandscapeData};
-use crate::build::data::DATE_FORMAT;
+use crate::{build::data::DATE_FORMAT, GraphFormat};
 use anyhow::Result;
 use chrono::NaiveDate;
 use serde::Serialize;
-use std::fs::File;
+use std::{collections::BTreeMap, fs::File, io::Write};
 
 /// Item information used for each record in the CSV file.
 ///
@@ -184,12 +184,118 @@ impl From<&data::Item> for Item {
     }
 }
 
-/// Generate CSV file with some information about each item.
-pub(crate) fn generate_items_csv(mut w: csv::Writer<File>, landscape_data: &LandscapeData) -> Result<()> {
+/// Generate CSV file with some information about each item. Items are sorted
+/// by name unless the order in the landscape data must be kept.
+pub(crate) fn generate_items_csv(
+    mut w: csv::Writer<File>,
+    landscape_data: &LandscapeData,
+    keep_order: bool,
+) -> Result<()> {
     let mut items: Vec<Item> = landscape_data.items.iter().map(Item::from).collect();
-    items.sort_by_key(|i| i.name.to_lowercase());
+    if !keep_order {
+        items.sort_by_key(|i| i.name.to_lowercase());
+    }
     items.iter().try_for_each(|i| w.serialize(i))?;
     w.flush()?;
 
     Ok(())
 }
+
+/// Generate a graph file representing the relationships between the items in
+/// the landscape, their organizations and their categories.
+pub(crate) fn generate_graph(
+    mut w: impl Write,
+    landscape_data: &LandscapeData,
+    format: GraphFormat,
+) -> Result<()> {
+    // Prepare graph nodes (id -> (kind, label)) and edges (source, target, kind)
+    let mut nodes: BTreeMap<String, (&str, String)> = BTreeMap::new();
+    let mut edges: Vec<(String, String, &str)> = vec![];
+    for item in &landscape_data.items {
+        let item_id = item.id.to_string();
+        nodes.insert(item_id.clone(), ("item", item.name.clone()));
+
+        let category_id = format!("category:{}", item.category);
+        nodes.insert(category_id.clone(), ("category", item.category.clone()));
+        edges.push((item_id.clone(), category_id, "category"));
+
+        if let Some(crunchbase_url) = &item.crunchbase_url {
+            // Use organization's name as label when available (the Crunchbase
+            // url is used otherwise)
+            let organization_id = format!("organization:{crunchbase_url}");
+            let organization_name = item.crunchbase_data.as_ref().and_then(|org| org.name.clone());
+            if let Some(name) = organization_name {
+                nodes.insert(organization_id.clone(), ("organization", name));
+            } else {
+                nodes.entry(organization_id.clone()).or_insert(("organization", crunchbase_url.clone()));
+            }
+            edges.push((item_id, organization_id, "organization"));
+        }
+    }
+
+    // Write graph in the format requested
+    match format {
+        GraphFormat::Dot => {
+            let escape = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+            writeln!(w, "digraph landscape {{")?;
+            for (id, (kind, label)) in &nodes {
+                writeln!(
+                    w,
+                    r#"  "{}" [label="{}", kind="{kind}"];"#,
+                    escape(id),
+                    escape(label)
+                )?;
+            }
+            for (source, target, kind) in &edges {
+                writeln!(
+                    w,
+                    r#"  "{}" -> "{}" [kind="{kind}"];"#,
+                    escape(source),
+                    escape(target)
+                )?;
+            }
+            writeln!(w, "}}")?;
+        }
+        GraphFormat::Graphml => {
+            let escape = |s: &str| {
+                s.replace('&', "&amp;")
+                    .replace('<', "&lt;")
+                    .replace('>', "&gt;")
+                    .replace('"', "&quot;")
+                    .replace('\'', "&apos;")
+            };
+            writeln!(w, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+            writeln!(w, r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#)?;
+            writeln!(
+                w,
+                r#"  <key id="label" for="node" attr.name="label" attr.type="string"/>"#
+            )?;
+            writeln!(
+                w,
+                r#"  <key id="kind" for="all" attr.name="kind" attr.type="string"/>"#
+            )?;
+            writeln!(w, r#"  <graph id="landscape" edgedefault="directed">"#)?;
+            for (id, (kind, label)) in &nodes {
+                writeln!(w, r#"    <node id="{}">"#, escape(id))?;
+                writeln!(w, r#"      <data key="label">{}</data>"#, escape(label))?;
+                writeln!(w, r#"      <data key="kind">{kind}</data>"#)?;
+                writeln!(w, "    </node>")?;
+            }
+            for (source, target, kind) in &edges {
+                writeln!(
+                    w,
+                    r#"    <edge source="{}" target="{}">"#,
+                    escape(source),
+                    escape(target)
+                )?;
+                writeln!(w, r#"      <data key="kind">{kind}</data>"#)?;
+                writeln!(w, "    </edge>")?;
+            }
+            writeln!(w, "  </graph>")?;
+            writeln!(w, "</graphml>")?;
+        }
+    }
+
+    w.flush()?;
+    Ok(())
+}
@@ -2,23 +2,33 @@
 //! from GitHub for each of the landscape items repositories (when applicable),
 //! as well as the functionality used to collect that information.
 
-use super::{cache::Cache, LandscapeData};
-use anyhow::{format_err, Result};
+use super::{
+    cache::DynCache,
+    concurrency, metrics, refresh,
+    warnings::{self, WarningKind},
+    LandscapeData,
+};
+use anyhow::{format_err, Context, Result};
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use deadpool::unmanaged::{Object, Pool};
 use futures::stream::{self, StreamExt};
 use lazy_static::lazy_static;
 #[cfg(test)]
 use mockall::automock;
 use octorust::auth::Credentials;
-use octorust::types::{FullRepository, ParticipationStats};
+use octorust::types::{FullRepository, OrganizationFull, ParticipationStats};
 use regex::Regex;
-use reqwest::header;
+use reqwest::{header, StatusCode};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::env;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 use tracing::{debug, instrument, warn};
+use url::Url;
 
 /// File used to cache data collected from GitHub.
 const GITHUB_CACHE_FILE: &str = "github.json";
@@ -26,46 +36,68 @@ const GITHUB_CACHE_FILE: &str = "github.json";
 /// How long the GitHub data in the cache is valid (in days).
 const GITHUB_CACHE_TTL: i64 = 7;
 
+/// File used to cache organizations data collected from GitHub.
+const GITHUB_ORGS_CACHE_FILE: &str = "github_orgs.json";
+
+/// File used to cache the stars history of the repositories.
+const GITHUB_STARS_HISTORY_CACHE_FILE: &str = "github_stars_history.json";
+
+/// Maximum number of data points kept in each repository's stars history.
+const GITHUB_STARS_HISTORY_MAX_POINTS: usize = 100;
+
+/// Maximum number of times the commit activity stats are requested again while
+/// GitHub is still computing them.
+const GITHUB_STATS_MAX_RETRIES: u32 = 5;
+
+/// Time to wait before requesting the commit activity stats again for the first
+/// time (it's doubled on each retry).
+const GITHUB_STATS_RETRY_DELAY: Duration = Duration::from_secs(2);
+
 /// Environment variable containing a comma separated list of GitHub tokens.
 const GITHUB_TOKENS: &str = "GITHUB_TOKENS";
 
 /// Collect GitHub data for each of the items repositories in the landscape,
-/// reusing cached data whenever possible.
+/// reusing cached data whenever possible. A custom GitHub API base url can be
+/// provided to collect data from a GitHub Enterprise Server instance. When an
+/// activity window (in days) is provided, the issues and pull requests
+/// activity of the repositories will be collected as well. Similarly, the
+/// weekly number of commits during the last year will be collected when the
+/// commit activity is requested.
 #[instrument(skip_all, err)]
-pub(crate) async fn collect_github_data(cache: &Cache, landscape_data: &LandscapeData) -> Result<GithubData> {
+pub(crate) async fn collect_github_data(
+    cache: &DynCache,
+    landscape_data: &LandscapeData,
+    api_url: &Option<String>,
+    tokens_file: &Option<PathBuf>,
+    activity_days: Option<i64>,
+    commit_activity: bool,
+) -> Result<GithubData> {
     debug!("collecting repositories information from github (this may take a while)");
 
+    // Prepare GitHub API url and host where the repositories are hosted
+    let api_url = api_url.as_deref().unwrap_or(GITHUB_API_URL).trim_end_matches('/');
+    let host = get_host(api_url)?;
+
     // Read cached data (if available)
     let mut cached_data: Option<GithubData> = None;
-    if let Ok(Some(json_data)) = cache.read(GITHUB_CACHE_FILE) {
+    if let Ok(Some(json_data)) = cache.read(GITHUB_CACHE_FILE).await {
         if let Ok(github_data) = serde_json::from_slice(&json_data) {
             cached_data = Some(github_data);
         }
     };
 
     // Setup GitHub API clients pool if any tokens have been provided
-    let tokens: Option<Vec<String>> = match env::var(GITHUB_TOKENS) {
-        Ok(tokens) if !tokens.is_empty() => Some(tokens.split(',').map(ToString::to_string).collect()),
-        Ok(_) | Err(_) => None,
-    };
-    let gh_pool: Option<Pool<DynGH>> = if let Some(tokens) = &tokens {
-        let mut gh_clients: Vec<DynGH> = vec![];
-        for token in tokens {
-            let gh = Box::new(GHApi::new(token)?);
-            gh_clients.push(gh);
-        }
-        Some(Pool::from(gh_clients))
-    } else {
+    let (gh_pool, concurrency) = setup_gh_pool(api_url, tokens_file.as_deref())?;
+    if gh_pool.is_none() {
         warn!("github tokens not provided: no information will be collected from github");
-        None
-    };
+    }
 
     // Collect urls of the repositories to process
     let mut urls = vec![];
     for item in &landscape_data.items {
         if let Some(repositories) = &item.repositories {
             for repo in repositories {
-                if GITHUB_REPO_URL.is_match(&repo.url) {
+                if get_owner_and_repo(&repo.url, &host).is_ok() {
                     urls.push(&repo.url);
                 }
             }
@@ -75,17 +107,17 @@ pub(crate) async fn collect_github_data(cache: &Cache, landscape_data: &Landscap
     urls.dedup();
 
     // Collect repositories information from GitHub, reusing cached data when available
-    let concurrency = if let Some(tokens) = tokens {
-        tokens.len()
-    } else {
-        1
-    };
-    let github_data: GithubData = stream::iter(urls)
+    let mut github_data: GithubData = stream::iter(urls)
         .map(|url| async {
             let url = url.clone();
             if let Some(cached_repo) = cached_data.as_ref().and_then(|cache| {
                 cache.get(&url).and_then(|repo| {
-                    if repo.generated_at + chrono::Duration::days(GITHUB_CACHE_TTL) > Utc::now() {
+                    // Cached data is not used if the activity requested is different
+                    let activity_window = repo.activity.as_ref().map(|activity| activity.window_days);
+                    if refresh::is_cached_data_valid(&url, repo.generated_at, GITHUB_CACHE_TTL)
+                        && activity_window == activity_days
+                        && repo.commit_activity.is_some() == commit_activity
+                    {
                         Some(repo)
                     } else {
                         None
@@ -97,8 +129,20 @@ pub(crate) async fn collect_github_data(cache: &Cache, landscape_data: &Landscap
             } else {
                 // Otherwise we pull it from GitHub if any tokens were provided
                 if let Some(gh_pool) = &gh_pool {
-                    let gh = gh_pool.get().await.expect("token -when available-");
-                    (url.clone(), Repository::new(gh, &url).await)
+                    let result = {
+                        let _permit = concurrency::acquire().await;
+                        let gh = gh_pool.get().await.expect("token -when available-");
+                        Repository::new(gh, &url, &host, activity_days).await
+                    };
+                    metrics::record_external_data_fetch("github", result.is_ok());
+                    let result = match result {
+                        Ok(mut repo) if commit_activity => {
+                            repo.commit_activity = collect_commit_activity(gh_pool, &url, &host).await;
+                            Ok(repo)
+                        }
+                        result => result,
+                    };
+                    (url.clone(), result)
                 } else {
                     (url.clone(), Err(format_err!("no tokens provided")))
                 }
@@ -108,27 +152,337 @@ pub(crate) async fn collect_github_data(cache: &Cache, landscape_data: &Landscap
         .collect::<HashMap<String, Result<Repository>>>()
         .await
         .into_iter()
-        .filter_map(|(url, result)| {
-            if let Ok(github_data) = result {
-                Some((url, github_data))
-            } else {
+        .filter_map(|(url, result)| match result {
+            Ok(github_data) => Some((url, github_data)),
+            Err(err) => {
+                if gh_pool.is_some() {
+                    let msg = format!("error collecting github data for {url}: {err}");
+                    warnings::add(WarningKind::ExternalData, None, msg);
+                }
                 None
             }
         })
         .collect();
 
     // Write data (in json format) to cache
-    cache.write(GITHUB_CACHE_FILE, &serde_json::to_vec_pretty(&github_data)?)?;
+    cache.write(GITHUB_CACHE_FILE, &serde_json::to_vec_pretty(&github_data)?).await?;
+
+    // Update repositories stars history and add it to the data collected
+    add_stars_history(cache, &mut github_data).await?;
 
     Ok(github_data)
 }
 
+/// Collect the weekly number of commits during the last year of the repository
+/// provided. GitHub computes these stats in the background, so they are
+/// requested again a few times (waiting a bit longer each time) while they are
+/// not ready yet. The token and the concurrency permit are released while
+/// waiting. None is returned if the stats could not be collected.
+async fn collect_commit_activity(gh_pool: &Pool<DynGH>, repo_url: &str, host: &str) -> Option<Vec<i64>> {
+    let (owner, repo) = get_owner_and_repo(repo_url, host).ok()?;
+    let mut delay = GITHUB_STATS_RETRY_DELAY;
+    for attempt in 0..=GITHUB_STATS_MAX_RETRIES {
+        if attempt > 0 {
+            tokio::time::sleep(delay).await;
+            delay *= 2;
+        }
+        let result = {
+            let _permit = concurrency::acquire().await;
+            let gh = gh_pool.get().await.expect("token -when available-");
+            gh.get_commit_activity(&owner, &repo).await
+        };
+        match result {
+            Ok(Some(commit_activity)) => return Some(commit_activity),
+            Ok(None) => {}
+            Err(err) => {
+                let msg = format!("error collecting github commit activity for {repo_url}: {err}");
+                warnings::add(WarningKind::ExternalData, None, msg);
+                return None;
+            }
+        }
+    }
+
+    let msg = format!("github commit activity for {repo_url} not available yet");
+    warnings::add(WarningKind::ExternalData, None, msg);
+    None
+}
+
+/// Collect GitHub data for the organizations owning the landscape items'
+/// primary repositories, reusing cached data whenever possible. Repositories
+/// owned by users instead of organizations are ignored.
+#[instrument(skip_all, err)]
+pub(crate) async fn collect_github_orgs_data(
+    cache: &DynCache,
+    landscape_data: &LandscapeData,
+    api_url: &Option<String>,
+    tokens_file: &Option<PathBuf>,
+) -> Result<GithubOrgsData> {
+    debug!("collecting organizations information from github");
+
+    // Prepare GitHub API url and host where the repositories are hosted
+    let api_url = api_url.as_deref().unwrap_or(GITHUB_API_URL).trim_end_matches('/');
+    let host = get_host(api_url)?;
+
+    // Read cached data (if available)
+    let mut cached_data: Option<GithubOrgsData> = None;
+    if let Ok(Some(json_data)) = cache.read(GITHUB_ORGS_CACHE_FILE).await {
+        if let Ok(github_orgs_data) = serde_json::from_slice(&json_data) {
+            cached_data = Some(github_orgs_data);
+        }
+    }
+
+    // Setup GitHub API clients pool if any tokens have been provided
+    let (gh_pool, concurrency) = setup_gh_pool(api_url, tokens_file.as_deref())?;
+
+    // Collect owners of the primary repositories to process
+    let mut owners = vec![];
+    for item in &landscape_data.items {
+        if let Some(repo) = item.primary_repository() {
+            if let Ok((owner, _)) = get_owner_and_repo(&repo.url, &host) {
+                owners.push(owner);
+            }
+        }
+    }
+    owners.sort();
+    owners.dedup();
+
+    // Collect organizations information from GitHub, reusing cached data when available
+    let github_orgs_data: GithubOrgsData = stream::iter(owners)
+        .map(|login| async {
+            if let Some(cached_org) = cached_data.as_ref().and_then(|cache| {
+                cache.get(&login).and_then(|org| {
+                    if refresh::is_cached_data_valid(&login, org.generated_at, GITHUB_CACHE_TTL) {
+                        Some(org)
+                    } else {
+                        None
+                    }
+                })
+            }) {
+                // Use cached data when available if it hasn't expired yet
+                (login, Ok(Some(cached_org.clone())))
+            } else {
+                // Otherwise we pull it from GitHub if any tokens were provided
+                if let Some(gh_pool) = &gh_pool {
+                    let _permit = concurrency::acquire().await;
+                    let gh = gh_pool.get().await.expect("token -when available-");
+                    let result = GithubOrganization::new(gh, &login).await;
+                    metrics::record_external_data_fetch("github_orgs", result.is_ok());
+                    (login, result)
+                } else {
+                    (login, Err(format_err!("no tokens provided")))
+                }
+            }
+        })
+        .buffer_unordered(concurrency)
+        .collect::<HashMap<String, Result<Option<GithubOrganization>>>>()
+        .await
+        .into_iter()
+        .filter_map(|(login, result)| match result {
+            Ok(org) => org.map(|org| (login, org)),
+            Err(err) => {
+                if gh_pool.is_some() {
+                    let msg = format!("error collecting github data for organization {login}: {err}");
+                    warnings::add(WarningKind::ExternalData, None, msg);
+                }
+                None
+            }
+        })
+        .collect();
+
+    // Write data (in json format) to cache
+    cache
+        .write(
+            GITHUB_ORGS_CACHE_FILE,
+            &serde_json::to_vec_pretty(&github_orgs_data)?,
+        )
+        .await?;
+
+    Ok(github_orgs_data)
+}
+
+/// Get the host where the repositories are hosted from the GitHub API url
+/// provided (the public GitHub API is used when it is not provided).
+pub(crate) fn get_repositories_host(api_url: Option<&str>) -> Result<String> {
+    get_host(api_url.unwrap_or(GITHUB_API_URL).trim_end_matches('/'))
+}
+
+/// Get the host where the repositories are hosted from the GitHub API url
+/// provided.
+fn get_host(api_url: &str) -> Result<String> {
+    if api_url == GITHUB_API_URL {
+        return Ok(GITHUB_HOST.to_string());
+    }
+    let host = Url::parse(api_url)?
+        .host_str()
+        .ok_or_else(|| format_err!("invalid github api url: {api_url}"))?
+        .to_string();
+    Ok(host)
+}
+
+/// Check the GitHub API is reachable and that all the tokens provided are
+/// authorized, returning the number of tokens checked (None is returned when
+/// no tokens have been provided).
+#[instrument(skip_all, err)]
+pub(crate) async fn check_github(
+    api_url: &Option<String>,
+    tokens_file: &Option<PathBuf>,
+) -> Result<Option<usize>> {
+    let api_url = api_url.as_deref().unwrap_or(GITHUB_API_URL).trim_end_matches('/');
+    let Some(tokens) = get_tokens(tokens_file.as_deref())? else {
+        return Ok(None);
+    };
+
+    for (i, token) in tokens.iter().enumerate() {
+        let gh = GHApi::new(api_url, token)?;
+        let remaining =
+            gh.get_rate_limit_remaining().await.context(format!("token #{} check failed", i + 1))?;
+        debug!(token = i + 1, remaining, "github token authorized");
+    }
+
+    Ok(Some(tokens.len()))
+}
+
+/// Setup a GitHub API clients pool if any tokens have been provided. The
+/// concurrency that can be used with the pool is returned as well.
+fn setup_gh_pool(api_url: &str, tokens_file: Option<&Path>) -> Result<(Option<Pool<DynGH>>, usize)> {
+    let Some(tokens) = get_tokens(tokens_file)? else {
+        return Ok((None, 1));
+    };
+
+    let mut gh_clients: Vec<DynGH> = vec![];
+    for token in &tokens {
+        let gh = Box::new(GHApi::new(api_url, token)?);
+        gh_clients.push(gh);
+    }
+    Ok((Some(Pool::from(gh_clients)), tokens.len()))
+}
+
+/// Get the GitHub tokens from the file provided or, when no file is provided,
+/// from the GITHUB_TOKENS environment variable.
+fn get_tokens(tokens_file: Option<&Path>) -> Result<Option<Vec<String>>> {
+    let tokens = if let Some(file) = tokens_file {
+        fs::read_to_string(file).context("error reading github tokens file")?
+    } else {
+        env::var(GITHUB_TOKENS).unwrap_or_default()
+    };
+    let tokens: Vec<String> = tokens
+        .split([',', '\n'])
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+        .map(ToString::to_string)
+        .collect();
+
+    Ok((!tokens.is_empty()).then_some(tokens))
+}
+
+/// Update the stars history of the repositories provided with the stars count
+/// collected and add it to their data. The history is persisted in the cache,
+/// so it grows as new data is collected over time.
+#[instrument(skip_all, err)]
+async fn add_stars_history(cache: &DynCache, github_data: &mut GithubData) -> Result<()> {
+    // Read cached stars history (if available)
+    let mut stars_history: HashMap<RepositoryUrl, StarsHistory> = HashMap::new();
+    if let Ok(Some(json_data)) = cache.read(GITHUB_STARS_HISTORY_CACHE_FILE).await {
+        if let Ok(cached_stars_history) = serde_json::from_slice(&json_data) {
+            stars_history = cached_stars_history;
+        }
+    }
+
+    // Add stars count to each repository history, using the date the data
+    // was collected (cached data may have been collected some days ago)
+    for (url, repo) in &mut *github_data {
+        let repo_stars_history = stars_history.entry(url.clone()).or_default();
+        repo_stars_history.insert(repo.generated_at.date_naive(), repo.stars);
+        while repo_stars_history.len() > GITHUB_STARS_HISTORY_MAX_POINTS {
+            repo_stars_history.pop_first();
+        }
+        repo.stars_history = Some(repo_stars_history.clone());
+    }
+
+    // Write stars history (in json format) to cache
+    cache
+        .write(
+            GITHUB_STARS_HISTORY_CACHE_FILE,
+            &serde_json::to_vec_pretty(&stars_history)?,
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Remove from the cached stars history the repositories that are not part of
+/// the landscape anymore. The number of bytes reclaimed is returned.
+#[instrument(skip_all, err)]
+pub(crate) async fn prune_stars_history(cache: &DynCache, landscape_data: &LandscapeData) -> Result<u64> {
+    let Some(json_data) = cache.read(GITHUB_STARS_HISTORY_CACHE_FILE).await? else {
+        return Ok(0);
+    };
+    let mut stars_history: HashMap<RepositoryUrl, StarsHistory> = serde_json::from_slice(&json_data)?;
+
+    // Keep only the history of the repositories in the landscape
+    let urls: HashSet<&RepositoryUrl> = landscape_data
+        .items
+        .iter()
+        .filter_map(|item| item.repositories.as_ref())
+        .flatten()
+        .map(|repo| &repo.url)
+        .collect();
+    stars_history.retain(|url, _| urls.contains(url));
+
+    // Write pruned stars history (in json format) to cache
+    let pruned_json_data = serde_json::to_vec_pretty(&stars_history)?;
+    cache.write(GITHUB_STARS_HISTORY_CACHE_FILE, &pruned_json_data).await?;
+
+    Ok(json_data.len().saturating_sub(pruned_json_data.len()) as u64)
+}
+
+/// Check that the values of the GitHub data provided are plausible (i.e. no
+/// negative stars counts or commits dates in the future), as implausible
+/// values usually indicate a parsing bug or a bad API response. A warning is
+/// reported for each of the implausible values found.
+pub(crate) fn check_github_data(github_data: &GithubData) {
+    let max_ts = Utc::now() + chrono::Duration::days(1);
+    for (url, repo) in github_data {
+        let mut issues = vec![];
+        if repo.stars < 0 {
+            issues.push(format!("negative stars count ({})", repo.stars));
+        }
+        for (name, ts) in [
+            ("first commit", repo.first_commit.ts),
+            ("latest commit", repo.latest_commit.ts),
+            ("latest release", repo.latest_release.as_ref().and_then(|r| r.ts)),
+        ] {
+            if let Some(ts) = ts.filter(|ts| *ts > max_ts) {
+                issues.push(format!("{name} date in the future ({ts})"));
+            }
+        }
+        if let (Some(first), Some(latest)) = (repo.first_commit.ts, repo.latest_commit.ts) {
+            if first > latest {
+                issues.push(format!("first commit ({first}) after the latest one ({latest})"));
+            }
+        }
+        if let Some(activity) = &repo.activity {
+            if activity.window_days <= 0 {
+                issues.push(format!("invalid activity window ({} days)", activity.window_days));
+            }
+        }
+        for issue in issues {
+            warn!(?url, issue, "implausible github data");
+            let msg = format!("implausible github data for {url}: {issue}");
+            warnings::add(WarningKind::ImplausibleData, None, msg);
+        }
+    }
+}
+
 /// Type alias to represent some repositories' GitHub data.
 pub(crate) type GithubData = HashMap<RepositoryUrl, Repository>;
 
 /// Type alias to represent a GitHub repository url.
 pub(crate) type RepositoryUrl = String;
 
+/// Type alias to represent a repository's stars history (stars count by date).
+pub(crate) type StarsHistory = BTreeMap<NaiveDate, i64>;
+
 /// Repository information collected from GitHub.
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub(crate) struct Repository {
@@ -141,6 +495,12 @@ pub(crate) struct Repository {
     pub stars: i64,
     pub url: String,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activity: Option<Activity>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_activity: Option<Vec<i64>>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub languages: Option<HashMap<String, i64>>,
 
@@ -149,13 +509,22 @@ pub(crate) struct Repository {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub license: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary_language: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stars_history: Option<StarsHistory>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub topics: Option<Vec<String>>,
 }
 
 impl Repository {
     /// Create a new Repository instance from information available on GitHub.
-    async fn new(gh: Object<DynGH>, repo_url: &str) -> Result<Self> {
+    async fn new(gh: Object<DynGH>, repo_url: &str, host: &str, activity_days: Option<i64>) -> Result<Self> {
         // Collect some information from GitHub
-        let (owner, repo) = get_owner_and_repo(repo_url)?;
+        let (owner, repo) = get_owner_and_repo(repo_url, host)?;
         let gh_repo = gh.get_repository(&owner, &repo).await?;
         let contributors_count = gh.get_contributors_count(&owner, &repo).await?;
         let first_commit = gh.get_first_commit(&owner, &repo, &gh_repo.default_branch).await?;
@@ -163,13 +532,37 @@ impl Repository {
         let latest_commit = gh.get_latest_commit(&owner, &repo, &gh_repo.default_branch).await?;
         let latest_release = gh.get_latest_release(&owner, &repo).await?;
         let participation_stats = gh.get_participation_stats(&owner, &repo).await?.all;
+        let activity = match activity_days {
+            // Activity is optional, so the repository information is still
+            // collected when it is not available (i.e. issues are disabled)
+            Some(window_days) => match gh.get_activity(&owner, &repo, window_days).await {
+                Ok(activity) => Some(activity),
+                Err(err) => {
+                    let msg = format!("error collecting github activity for {repo_url}: {err}");
+                    warnings::add(WarningKind::ExternalData, None, msg);
+                    None
+                }
+            },
+            None => None,
+        };
+
+        // The primary language is the one with the largest number of bytes
+        // (ties are resolved alphabetically so that the result is stable)
+        let primary_language = languages.as_ref().and_then(|languages| {
+            languages
+                .iter()
+                .max_by(|(name1, bytes1), (name2, bytes2)| bytes1.cmp(bytes2).then(name2.cmp(name1)))
+                .map(|(name, _)| name.clone())
+        });
 
         // Prepare repository instance using the information collected
         Ok(Repository {
+            activity,
+            commit_activity: None,
             generated_at: Utc::now(),
             contributors: Contributors {
                 count: contributors_count,
-                url: format!("https://github.com/{owner}/{repo}/graphs/contributors"),
+                url: format!("{}/graphs/contributors", gh_repo.html_url.trim_end_matches('/')),
             },
             description: gh_repo.description,
             first_commit,
@@ -184,12 +577,76 @@ impl Repository {
                 }
             }),
             participation_stats,
+            primary_language,
             stars: gh_repo.stargazers_count,
+            stars_history: None,
+            topics: Some(gh_repo.topics).filter(|topics| !topics.is_empty()),
             url: gh_repo.html_url,
         })
     }
 }
 
+/// Type alias to represent some organizations' GitHub data.
+pub(crate) type GithubOrgsData = HashMap<OrganizationLogin, GithubOrganization>;
+
+/// Type alias to represent a GitHub organization login.
+pub(crate) type OrganizationLogin = String;
+
+/// Organization information collected from GitHub.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct GithubOrganization {
+    pub generated_at: DateTime<Utc>,
+    pub login: String,
+    pub members_count: usize,
+    pub public_repos: i64,
+    pub url: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+impl GithubOrganization {
+    /// Create a new GithubOrganization instance from information available
+    /// on GitHub. None is returned if the login provided does not belong to
+    /// an organization.
+    async fn new(gh: Object<DynGH>, login: &str) -> Result<Option<Self>> {
+        // Collect some information from GitHub
+        let Some(gh_org) = gh.get_organization(login).await? else {
+            return Ok(None);
+        };
+        let members_count = gh.get_public_members_count(login).await?;
+
+        // Prepare organization instance using the information collected
+        let non_empty = |value: String| if value.is_empty() { None } else { Some(value) };
+        Ok(Some(GithubOrganization {
+            generated_at: Utc::now(),
+            description: non_empty(gh_org.description),
+            location: non_empty(gh_org.location),
+            login: gh_org.login,
+            members_count,
+            name: non_empty(gh_org.name),
+            public_repos: gh_org.public_repos,
+            url: gh_org.html_url,
+        }))
+    }
+}
+
+/// Issues and pull requests activity during the time window provided.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Activity {
+    pub closed_issues: usize,
+    pub closed_pull_requests: usize,
+    pub opened_issues: usize,
+    pub opened_pull_requests: usize,
+    pub window_days: i64,
+}
+
 /// Commit information.
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub(crate) struct Commit {
@@ -236,6 +693,9 @@ impl From<octorust::types::Release> for Release {
 /// GitHub API base url.
 const GITHUB_API_URL: &str = "https://api.github.com";
 
+/// GitHub host.
+pub(crate) const GITHUB_HOST: &str = "github.com";
+
 /// Type alias to represent a GH trait object.
 type DynGH = Box<dyn GH + Send + Sync>;
 
@@ -243,6 +703,13 @@ type DynGH = Box<dyn GH + Send + Sync>;
 #[async_trait]
 #[cfg_attr(test, automock)]
 trait GH {
+    /// Get issues and pull requests activity during the last days provided.
+    async fn get_activity(&self, owner: &str, repo: &str, window_days: i64) -> Result<Activity>;
+
+    /// Get the weekly number of commits during the last year (oldest first).
+    /// None is returned if GitHub is still computing the stats.
+    async fn get_commit_activity(&self, owner: &str, repo: &str) -> Result<Option<Vec<i64>>>;
+
     /// Get number of repository contributors.
     async fn get_contributors_count(&self, owner: &str, repo: &str) -> Result<usize>;
 
@@ -258,25 +725,38 @@ trait GH {
     /// Get latest release.
     async fn get_latest_release(&self, owner: &str, repo: &str) -> Result<Option<Release>>;
 
+    /// Get organization (None is returned if it does not exist).
+    async fn get_organization(&self, org: &str) -> Result<Option<OrganizationFull>>;
+
     /// Get participation stats.
     async fn get_participation_stats(&self, owner: &str, repo: &str) -> Result<ParticipationStats>;
 
+    /// Get number of requests remaining in the current rate limit window.
+    async fn get_rate_limit_remaining(&self) -> Result<i64>;
+
+    /// Get number of organization public members.
+    async fn get_public_members_count(&self, org: &str) -> Result<usize>;
+
     /// Get repository.
     async fn get_repository(&self, owner: &str, repo: &str) -> Result<FullRepository>;
 }
 
 /// GH implementation backed by the GitHub API.
 struct GHApi {
+    api_url: String,
     gh_client: octorust::Client,
     http_client: reqwest::Client,
 }
 
 impl GHApi {
     /// Create a new GHApi instance.
-    fn new(token: &str) -> Result<Self> {
+    fn new(api_url: &str, token: &str) -> Result<Self> {
         // Setup octorust GitHub API client
         let user_agent = format!("{}/{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
-        let gh_client = octorust::Client::new(user_agent.clone(), Credentials::Token(token.to_string()))?;
+        let mut gh_client = octorust::Client::new(user_agent.clone(), Credentials::Token(token.to_string()))?;
+        if api_url != GITHUB_API_URL {
+            gh_client.with_host_override(api_url);
+        }
 
         // Setup HTTP client ready to make requests to the GitHub API
         // (for some operations that cannot be done with the octorust client)
@@ -297,6 +777,7 @@ impl GHApi {
             reqwest::Client::builder().user_agent(user_agent).default_headers(headers).build()?;
 
         Ok(Self {
+            api_url: api_url.to_string(),
             gh_client,
             http_client,
         })
@@ -305,11 +786,96 @@ impl GHApi {
 
 #[async_trait]
 impl GH for GHApi {
+    /// [GH::get_activity]
+    #[instrument(fields(?owner, ?repo), skip_all, err)]
+    async fn get_activity(&self, owner: &str, repo: &str, window_days: i64) -> Result<Activity> {
+        /// Issue (or pull request) information returned by the GitHub API.
+        #[derive(Deserialize)]
+        struct Issue {
+            created_at: DateTime<Utc>,
+            closed_at: Option<DateTime<Utc>>,
+            pull_request: Option<serde_json::Value>,
+        }
+
+        let mut activity = Activity {
+            window_days,
+            ..Default::default()
+        };
+
+        // Issues and pull requests updated during the time window are listed
+        // page by page, counting the ones opened or closed during it
+        let since = Utc::now() - chrono::Duration::days(window_days);
+        let mut url = Some(format!(
+            "{}/repos/{owner}/{repo}/issues?state=all&since={}&per_page=100",
+            self.api_url,
+            since.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+        ));
+        while let Some(page_url) = url {
+            let response = self.http_client.get(page_url).send().await?;
+            if response.status() != StatusCode::OK {
+                return Err(format_err!("unexpected status code: {:?}", response.status()));
+            }
+            url = None;
+            if let Some(link_header) = response.headers().get("link") {
+                let rels = parse_link_header::parse_with_rel(link_header.to_str()?)?;
+                if let Some(next_page_url) = rels.get("next") {
+                    url = Some(next_page_url.raw_uri.clone());
+                }
+            }
+            let issues: Vec<Issue> = response.json().await?;
+            for issue in issues {
+                let is_pull_request = issue.pull_request.is_some();
+                if issue.created_at >= since {
+                    if is_pull_request {
+                        activity.opened_pull_requests += 1;
+                    } else {
+                        activity.opened_issues += 1;
+                    }
+                }
+                if issue.closed_at.is_some_and(|closed_at| closed_at >= since) {
+                    if is_pull_request {
+                        activity.closed_pull_requests += 1;
+                    } else {
+                        activity.closed_issues += 1;
+                    }
+                }
+            }
+        }
+
+        Ok(activity)
+    }
+
+    /// [GH::get_commit_activity]
+    #[instrument(fields(?owner, ?repo), skip_all, err)]
+    async fn get_commit_activity(&self, owner: &str, repo: &str) -> Result<Option<Vec<i64>>> {
+        /// Week commit activity information returned by the GitHub API.
+        #[derive(Deserialize)]
+        struct Week {
+            total: i64,
+        }
+
+        // GitHub responds with a 202 status code while the stats are being
+        // computed
+        let url = format!("{}/repos/{owner}/{repo}/stats/commit_activity", self.api_url);
+        let response = self.http_client.get(url).send().await?;
+        match response.status() {
+            StatusCode::OK => {
+                let weeks: Vec<Week> = response.json().await?;
+                Ok(Some(weeks.into_iter().map(|week| week.total).collect()))
+            }
+            StatusCode::ACCEPTED => Ok(None),
+            status => Err(format_err!("unexpected status code: {:?}", status)),
+        }
+    }
+
     /// [GH::get_contributors_count]
     #[instrument(fields(?owner, ?repo), skip_all, err)]
     async fn get_contributors_count(&self, owner: &str, repo: &str) -> Result<usize> {
         let mut count = 1;
-        let url = format!("{GITHUB_API_URL}/repos/{owner}/{repo}/contributors?per_page=1&anon=true");
+        let url = format!(
+            "{}/repos/{owner}/{repo}/contributors?per_page=1&anon=true",
+            self.api_url
+        );
         let response = self.http_client.head(url).send().await?;
         if let Some(link_header) = response.headers().get("link") {
             let rels = parse_link_header::parse_with_rel(link_header.to_str()?)?;
@@ -327,7 +893,10 @@ impl GH for GHApi {
     async fn get_first_commit(&self, owner: &str, repo: &str, ref_: &str) -> Result<Commit> {
         // Get last commits page
         let mut last_page = 1;
-        let url = format!("{GITHUB_API_URL}/repos/{owner}/{repo}/commits?sha={ref_}&per_page=1");
+        let url = format!(
+            "{}/repos/{owner}/{repo}/commits?sha={ref_}&per_page=1",
+            self.api_url
+        );
         let response = self.http_client.head(url).send().await?;
         if let Some(link_header) = response.headers().get("link") {
             let rels = parse_link_header::parse_with_rel(link_header.to_str()?)?;
@@ -354,7 +923,7 @@ impl GH for GHApi {
     /// [GH::get_languages]
     #[instrument(fields(?owner, ?repo), skip_all, err)]
     async fn get_languages(&self, owner: &str, repo: &str) -> Result<Option<HashMap<String, i64>>> {
-        let url = format!("{GITHUB_API_URL}/repos/{owner}/{repo}/languages");
+        let url = format!("{}/repos/{owner}/{repo}/languages", self.api_url);
         let languages: HashMap<String, i64> = self.http_client.get(url).send().await?.json().await?;
         Ok(Some(languages))
     }
@@ -380,12 +949,57 @@ impl GH for GHApi {
         }
     }
 
+    /// [GH::get_organization]
+    #[instrument(fields(?org), skip_all, err)]
+    async fn get_organization(&self, org: &str) -> Result<Option<OrganizationFull>> {
+        match self.gh_client.orgs().get(org).await {
+            Ok(org) => Ok(Some(org)),
+            Err(err) => {
+                if err.to_string().to_lowercase().contains("not found") {
+                    return Ok(None);
+                }
+                Err(err)
+            }
+        }
+    }
+
     /// [GH::get_participation_stats]
     #[instrument(fields(?owner, ?repo), skip_all, err)]
     async fn get_participation_stats(&self, owner: &str, repo: &str) -> Result<ParticipationStats> {
         self.gh_client.repos().get_participation_stats(owner, repo).await
     }
 
+    /// [GH::get_rate_limit_remaining]
+    #[instrument(skip_all, err)]
+    async fn get_rate_limit_remaining(&self) -> Result<i64> {
+        let url = format!("{}/rate_limit", self.api_url);
+        let response = self.http_client.get(url).send().await?;
+        if response.status() != StatusCode::OK {
+            return Err(format_err!("unexpected status code: {:?}", response.status()));
+        }
+        let rate_limit: serde_json::Value = response.json().await?;
+        rate_limit["resources"]["core"]["remaining"]
+            .as_i64()
+            .ok_or_else(|| format_err!("invalid rate limit response"))
+    }
+
+    /// [GH::get_public_members_count]
+    #[instrument(fields(?org), skip_all, err)]
+    async fn get_public_members_count(&self, org: &str) -> Result<usize> {
+        let url = format!("{}/orgs/{org}/public_members?per_page=1", self.api_url);
+        let response = self.http_client.get(url).send().await?;
+        if let Some(link_header) = response.headers().get("link") {
+            let rels = parse_link_header::parse_with_rel(link_header.to_str()?)?;
+            if let Some(last_page_url) = rels.get("last") {
+                if let Some(value) = last_page_url.queries.get("page") {
+                    return Ok(value.parse()?);
+                }
+            }
+        }
+        let members: Vec<serde_json::Value> = response.json().await?;
+        Ok(members.len())
+    }
+
     /// [GH::get_repository]
     #[instrument(fields(?owner, ?repo), skip_all, err)]
     async fn get_repository(&self, owner: &str, repo: &str) -> Result<FullRepository> {
@@ -394,14 +1008,20 @@ impl GH for GHApi {
 }
 
 lazy_static! {
-    /// GitHub repository url regular expression.
-    pub(crate) static ref GITHUB_REPO_URL: Regex =
-        Regex::new("^https://github.com/(?P<owner>[^/]+)/(?P<repo>[^/]+)/?$")
-            .expect("exprs in GITHUB_REPO_URL to be valid");
+    /// Repository url regular expression (the host is captured so that the
+    /// repositories hosted on GitHub Enterprise Server instances can be
+    /// matched as well as the ones hosted on github.com).
+    static ref REPO_URL: Regex =
+        Regex::new("^https://(?P<host>[^/]+)/(?P<owner>[^/]+)/(?P<repo>[^/]+)/?$")
+            .expect("exprs in REPO_URL to be valid");
 }
 
-/// Extract the owner and repository from the repository url provided.
-fn get_owner_and_repo(repo_url: &str) -> Result<(String, String)> {
-    let c = GITHUB_REPO_URL.captures(repo_url).ok_or_else(|| format_err!("invalid repository url"))?;
+/// Extract the owner and repository from the repository url provided. An
+/// error is returned if the repository is not hosted on the host provided.
+pub(crate) fn get_owner_and_repo(repo_url: &str, host: &str) -> Result<(String, String)> {
+    let c = REPO_URL.captures(repo_url).ok_or_else(|| format_err!("invalid repository url"))?;
+    if &c["host"] != host {
+        return Err(format_err!("repository not hosted on {host}"));
+    }
     Ok((c["owner"].to_string(), c["repo"].to_string()))
 }
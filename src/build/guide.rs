@@ -1,49 +1,59 @@
 //! This module defines the types used to represent the landscape guide content
 //! that must be provided from a YAML file (guide.yml).
 
+use super::{
+    slugify,
+    warnings::{self, WarningKind},
+    LandscapeData,
+};
 use crate::GuideSource;
 use anyhow::{format_err, Result};
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
 use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
-use std::{fs, path::Path};
-use tracing::{debug, instrument};
+use std::{collections::HashSet, fs, path::Path};
+use tracing::{debug, instrument, warn};
 
 /// Landscape guide content.
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub(crate) struct LandscapeGuide {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub categories: Option<Vec<Category>>,
+
+    #[serde(default, skip_deserializing, skip_serializing_if = "Vec::is_empty")]
+    pub toc: Vec<TocEntry>,
 }
 
 impl LandscapeGuide {
     /// Create a new landscape guide instance from the source provided.
     #[instrument(skip_all, err)]
-    pub(crate) async fn new(src: &GuideSource) -> Result<Option<Self>> {
+    pub(crate) async fn new(src: &GuideSource, sanitizer: &ammonia::Builder<'_>) -> Result<Option<Self>> {
         // Try from file
         if let Some(file) = &src.guide_file {
             debug!(?file, "getting landscape guide from file");
-            return Ok(Some(LandscapeGuide::new_from_file(file)?));
+            return Ok(Some(LandscapeGuide::new_from_file(file, sanitizer)?));
         };
 
         // Try from url
         if let Some(url) = &src.guide_url {
             debug!(?url, "getting landscape guide from url");
-            return Ok(Some(LandscapeGuide::new_from_url(url).await?));
+            return Ok(Some(LandscapeGuide::new_from_url(url, sanitizer).await?));
         };
 
         Ok(None)
     }
 
     /// Create a new landscape guide instance from the file provided.
-    fn new_from_file(file: &Path) -> Result<Self> {
+    fn new_from_file(file: &Path, sanitizer: &ammonia::Builder<'_>) -> Result<Self> {
         let raw_data = fs::read_to_string(file)?;
-        let guide = LandscapeGuide::new_from_yaml(&raw_data)?;
+        let guide = LandscapeGuide::new_from_yaml(&raw_data, sanitizer)?;
 
         Ok(guide)
     }
 
     /// Create a new landscape guide instance from the url provided.
-    async fn new_from_url(url: &str) -> Result<Self> {
+    async fn new_from_url(url: &str, sanitizer: &ammonia::Builder<'_>) -> Result<Self> {
         let resp = reqwest::get(url).await?;
         if resp.status() != StatusCode::OK {
             return Err(format_err!(
@@ -52,34 +62,165 @@ impl LandscapeGuide {
             ));
         }
         let raw_data = resp.text().await?;
-        let guide = LandscapeGuide::new_from_yaml(&raw_data)?;
+        let guide = LandscapeGuide::new_from_yaml(&raw_data, sanitizer)?;
 
         Ok(guide)
     }
 
     /// Create a new landscape guide instance from the YAML string provided.
-    fn new_from_yaml(s: &str) -> Result<Self> {
+    fn new_from_yaml(s: &str, sanitizer: &ammonia::Builder<'_>) -> Result<Self> {
         // Parse YAML string
         let mut guide: LandscapeGuide = serde_yaml::from_str(s)?;
 
-        // Convert content fields from markdown to HTML
-        let options = markdown::Options::default();
+        // Convert content fields from markdown to (sanitized) HTML and build
+        // the table of contents from the categories, subcategories and the
+        // headings found in their content
+        let mut toc = Toc::default();
         if let Some(categories) = guide.categories.as_mut() {
             for c in &mut *categories {
-                c.content = markdown::to_html_with_options(&c.content, &options)
-                    .map_err(|err| format_err!("{err}"))?;
+                toc.add_entry(1, &c.category);
+                c.content = toc.add_headings(&markdown_to_html(&c.content, sanitizer)?);
 
                 if let Some(subcategories) = c.subcategories.as_mut() {
                     for sc in &mut *subcategories {
-                        sc.content = markdown::to_html_with_options(&sc.content, &options)
-                            .map_err(|err| format_err!("{err}"))?;
+                        toc.add_entry(2, &sc.subcategory);
+                        sc.content = toc.add_headings(&markdown_to_html(&sc.content, sanitizer)?);
                     }
                 }
             }
         }
+        guide.toc = toc.entries;
 
         Ok(guide)
     }
+
+    /// Check that the categories, subcategories and items referenced in the
+    /// guide exist in the landscape data provided. Items are referenced from
+    /// the content using links to the item view (i.e. `?item=<ID>`). A warning
+    /// is added for each of the references not found.
+    pub(crate) fn check_references(&self, landscape_data: &LandscapeData) {
+        let mut missing = vec![];
+
+        for c in self.categories.as_deref().unwrap_or_default() {
+            let data_category = landscape_data.categories.iter().find(|dc| dc.name == c.category);
+            match data_category {
+                Some(data_category) => {
+                    for sc in c.subcategories.as_deref().unwrap_or_default() {
+                        if !data_category.subcategories.contains(&sc.subcategory) {
+                            missing.push(format!("subcategory {} / {}", c.category, sc.subcategory));
+                        }
+                    }
+                }
+                None => missing.push(format!("category {}", c.category)),
+            }
+
+            let contents =
+                std::iter::once(&c.content).chain(c.subcategories.iter().flatten().map(|sc| &sc.content));
+            for content in contents {
+                for r in ITEM_REFERENCE.captures_iter(content) {
+                    let reference = &r["item"];
+                    if !landscape_data.items.iter().any(|i| i.id.to_string() == reference) {
+                        missing.push(format!("item {reference}"));
+                    }
+                }
+            }
+        }
+
+        for reference in missing {
+            warn!(reference, "guide reference not found in landscape data");
+            warnings::add(
+                WarningKind::Guide,
+                None,
+                format!("{reference} referenced in guide not found in landscape data"),
+            );
+        }
+    }
+}
+
+lazy_static! {
+    /// Regular expression used to match the headings in some HTML content.
+    static ref HTML_HEADING: Regex = Regex::new(r"(?s)<h([1-6])>(.*?)</h[1-6]>").expect("exprs in HTML_HEADING to be valid");
+
+    /// Regular expression used to match the references to items in the links
+    /// available in some HTML content.
+    static ref ITEM_REFERENCE: Regex = Regex::new(r#"href="[^"]*[?&](amp;)?item=(?P<item>[^"&#]+)"#).expect("exprs in ITEM_REFERENCE to be valid");
+
+    /// Regular expression used to match HTML tags.
+    static ref HTML_TAG: Regex = Regex::new(r"<[^>]*>").expect("exprs in HTML_TAG to be valid");
+}
+
+/// Guide table of contents builder. It keeps track of the ids used so far,
+/// so that the anchors are unique across the whole guide.
+#[derive(Debug, Default)]
+struct Toc {
+    entries: Vec<TocEntry>,
+    ids: HashSet<String>,
+}
+
+impl Toc {
+    /// Add an entry to the table of contents, returning its anchor id. When
+    /// the slug has already been used, a numeric suffix is appended to it.
+    fn add_entry(&mut self, level: u8, title: &str) -> String {
+        let slug = slugify(title);
+        let mut id = slug.clone();
+        let mut count = 0;
+        while !self.ids.insert(id.clone()) {
+            count += 1;
+            id = format!("{slug}-{count}");
+        }
+        self.entries.push(TocEntry {
+            id: id.clone(),
+            level,
+            title: title.to_string(),
+        });
+        id
+    }
+
+    /// Add the headings found in the HTML content provided to the table of
+    /// contents, returning the content with the corresponding anchor ids set
+    /// on the headings.
+    fn add_headings(&mut self, html: &str) -> String {
+        HTML_HEADING
+            .replace_all(html, |c: &Captures| {
+                let level: u8 = c[1].parse().unwrap_or(1);
+                let title = unescape_html(HTML_TAG.replace_all(&c[2], "").trim());
+                let id = self.add_entry(level, &title);
+                format!(r#"<h{level} id="{id}">{}</h{level}>"#, &c[2])
+            })
+            .to_string()
+    }
+}
+
+/// Unescape the basic HTML entities that may be present in some text.
+fn unescape_html(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Guide table of contents entry.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct TocEntry {
+    pub id: String,
+    pub level: u8,
+    pub title: String,
+}
+
+/// Convert the markdown content provided to HTML. Raw HTML is allowed in the
+/// markdown content, so the resulting HTML is sanitized before returning it.
+pub(crate) fn markdown_to_html(md: &str, sanitizer: &ammonia::Builder<'_>) -> Result<String> {
+    let options = markdown::Options {
+        compile: markdown::CompileOptions {
+            allow_dangerous_html: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let html = markdown::to_html_with_options(md, &options).map_err(|err| format_err!("{err}"))?;
+
+    Ok(sanitizer.clean(&html).to_string())
 }
 
 /// Guide category.
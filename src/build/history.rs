@@ -0,0 +1,141 @@
+//! This module provides the functionality used to build the history of the
+//! landscape from the Git repository the data file belongs to, tracking when
+//! items were added to or removed from the landscape.
+
+use super::{LandscapeData, DATASETS_PATH};
+use anyhow::{format_err, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
+use tokio::process::Command;
+use tracing::{debug, instrument, warn};
+use uuid::Uuid;
+
+/// Generate the landscape history from the commits of the Git repository
+/// that modified the data file provided, and write it to the output
+/// directory (history.json). Versions of the data file that cannot be parsed
+/// are skipped.
+#[instrument(skip_all, err)]
+pub(crate) async fn generate_history(data_file: &Path, output_dir: &Path) -> Result<()> {
+    debug!("generating landscape history");
+
+    // Locate data file in the repository
+    let (repo_dir, file_name) = split_data_file_path(data_file)?;
+    let prefix = git(&repo_dir, &["rev-parse", "--show-prefix"])
+        .await
+        .context("the data file provided is not in a git repository")?;
+    let path_in_repo = format!("{}{file_name}", prefix.trim());
+
+    // Get commits that modified the data file (oldest first)
+    let log = git(
+        &repo_dir,
+        &["log", "--reverse", "--format=%H %cI", "--", &file_name],
+    )
+    .await?;
+    let commits = log.lines().filter_map(|line| line.split_once(' '));
+
+    // Compare the items available in each of the data file versions
+    let mut history = vec![];
+    let mut previous_items: BTreeMap<Uuid, HistoryItem> = BTreeMap::new();
+    for (commit, date) in commits {
+        let object = format!("{commit}:{path_in_repo}");
+        let Ok(raw_data) = git(&repo_dir, &["show", &object]).await else {
+            continue;
+        };
+        let items = match LandscapeData::new_from_yaml(&raw_data) {
+            Ok(landscape_data) => landscape_data
+                .items
+                .into_iter()
+                .map(|item| {
+                    let history_item = HistoryItem {
+                        category: item.category,
+                        name: item.name,
+                        subcategory: item.subcategory,
+                    };
+                    (item.id, history_item)
+                })
+                .collect::<BTreeMap<Uuid, HistoryItem>>(),
+            Err(err) => {
+                warn!(?err, commit, "error parsing data file version, skipping it");
+                continue;
+            }
+        };
+
+        let entry = HistoryEntry {
+            commit: commit.to_string(),
+            date: date.to_string(),
+            added: items
+                .iter()
+                .filter(|(id, _)| !previous_items.contains_key(id))
+                .map(|(_, i)| i.clone())
+                .collect(),
+            removed: previous_items
+                .iter()
+                .filter(|(id, _)| !items.contains_key(id))
+                .map(|(_, i)| i.clone())
+                .collect(),
+        };
+        if !entry.added.is_empty() || !entry.removed.is_empty() {
+            history.push(entry);
+        }
+        previous_items = items;
+    }
+
+    // Write history file
+    let path = output_dir.join(DATASETS_PATH).join("history.json");
+    File::create(path)?.write_all(&serde_json::to_vec(&history)?)?;
+
+    Ok(())
+}
+
+/// Split the data file path provided into its directory and file name.
+fn split_data_file_path(data_file: &Path) -> Result<(PathBuf, String)> {
+    let file_name = data_file
+        .file_name()
+        .and_then(std::ffi::OsStr::to_str)
+        .ok_or_else(|| format_err!("invalid data file path"))?
+        .to_string();
+    let repo_dir = match data_file.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    Ok((repo_dir, file_name))
+}
+
+/// Run the git command provided in the directory given, returning its output.
+async fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git").arg("-C").arg(dir).args(args).output().await?;
+    if !output.status.success() {
+        return Err(format_err!(
+            "git command failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+/// Landscape history entry. Each entry represents a commit that modified the
+/// data file adding or removing some items.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct HistoryEntry {
+    commit: String,
+    date: String,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    added: Vec<HistoryItem>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    removed: Vec<HistoryItem>,
+}
+
+/// Landscape history item.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct HistoryItem {
+    category: String,
+    name: String,
+    subcategory: String,
+}
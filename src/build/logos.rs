@@ -1,16 +1,27 @@
 //! This module provides some helper functions to prepare logos to be displayed
 //! on the landscape web application.
 
-use super::cache::Cache;
+use super::{cache::DynCache, metrics, refresh};
 use crate::LogosSource;
 use anyhow::{format_err, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, Utc};
 use lazy_static::lazy_static;
 use regex::bytes::Regex;
 use reqwest::StatusCode;
+use resvg::tiny_skia::{FilterQuality, Pixmap, PixmapPaint, Transform};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::fs;
+use std::{
+    collections::HashMap,
+    fs,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use url::Url;
 use usvg::{NodeExt, Rect, TreeParsing};
+use uuid::Uuid;
 
 lazy_static! {
     /// Regular expression used to remove the SVG logos' title.
@@ -18,25 +29,224 @@ lazy_static! {
 
     /// Regular expression used to update the SVG logos' viewbox.
     static ref SVG_VIEWBOX: Regex = Regex::new(r#"viewBox="[0-9. ]*""#).expect("expr in SVG_VIEWBOX to be valid");
+
+    /// Regular expression used to check if an image is an SVG image.
+    static ref SVG_TAG: Regex = Regex::new("<svg[ >]").expect("expr in SVG_TAG to be valid");
+
+    /// Regular expression used to find the image elements in an SVG image.
+    static ref SVG_IMAGE: Regex = Regex::new(r"(?i)<image\s[^>]*>").expect("expr in SVG_IMAGE to be valid");
+
+    /// Regular expression used to extract the reference of an SVG image element.
+    static ref SVG_HREF: Regex = Regex::new(r#"(?:xlink:)?href\s*=\s*["'](?P<href>[^"']*)["']"#)
+        .expect("expr in SVG_HREF to be valid");
+
+    /// Regular expression used to find the links in an HTML document.
+    static ref HTML_LINK: regex::Regex = regex::Regex::new(r"(?i)<link\s[^>]*>").expect("expr in HTML_LINK to be valid");
+
+    /// Regular expression used to extract the attributes of an HTML tag.
+    static ref HTML_ATTR: regex::Regex = regex::Regex::new(r#"(?P<name>[a-zA-Z-]+)\s*=\s*["'](?P<value>[^"']*)["']"#)
+        .expect("expr in HTML_ATTR to be valid");
 }
 
+lazy_static! {
+    /// Semaphores used to limit the number of concurrent requests per host.
+    static ref HOSTS_SEMAPHORES: Mutex<HashMap<String, Arc<Semaphore>>> = Mutex::new(HashMap::new());
+}
+
+/// Maximum number of concurrent requests to the same host when fetching logos.
+const LOGOS_HOST_MAX_CONCURRENCY: usize = 4;
+
+/// Header of the Git LFS pointer files.
+const GIT_LFS_POINTER_HEADER: &[u8] = b"version https://git-lfs.github.com/spec/";
+
+/// Size (width and height, in pixels) of the sample used to compute the
+/// dominant color of the logos.
+const DOMINANT_COLOR_SAMPLE_SIZE: u32 = 32;
+
+/// Maximum size of the raster images embedded in SVG logos (in bytes).
+const SVG_EMBEDDED_RASTER_MAX_SIZE: usize = 50 * 1024;
+
+/// How long the logos obtained from a mirror are cached (in days).
+const LOGOS_MIRRORS_CACHE_TTL: i64 = 7;
+
 /// Represents some information about an item's logo.
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub(crate) struct Logo {
     pub svg_data: Vec<u8>,
     pub digest: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dominant_color: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub issues: Vec<String>,
+}
+
+/// Logo obtained from one of the item's mirrors, cached so that the source
+/// and the mirrors don't have to be tried again on every build.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct MirrorLogo {
+    generated_at: DateTime<Utc>,
+    mirror_url: String,
+    svg_data: String,
 }
 
 /// Get SVG logo from the source provided and apply some modifications to it.
+/// If the logo cannot be obtained from the source, the mirrors urls provided
+/// will be tried in order until one of them succeeds. The logo obtained from
+/// a mirror is cached using the item's id, and reused while it's still valid.
 pub(crate) async fn prepare_logo(
-    cache: &Cache,
+    cache: &DynCache,
     http_client: reqwest::Client,
     logos_source: &LogosSource,
+    item_id: Uuid,
     file_name: &str,
+    mirrors: &[String],
 ) -> Result<Logo> {
-    // Get SVG logo from the source provided
-    let mut svg_data = get_svg(http_client.clone(), logos_source, file_name).await?;
+    // Use the logo obtained from a mirror in a previous build if available
+    let mirror_cache_file = format!("logo_mirror_{item_id}.json");
+    let mut result = match get_cached_mirror_logo(cache, &mirror_cache_file, item_id, mirrors).await {
+        Some(svg_data) => Ok(svg_data),
+        None => {
+            // Get SVG logo from the source provided
+            get_svg(http_client.clone(), logos_source, file_name)
+                .await
+                .and_then(|svg_data| check_svg(file_name, svg_data))
+        }
+    };
+
+    // Try the mirrors if the logo could not be obtained from the source
+    for mirror_url in mirrors {
+        let Err(err) = result else {
+            break;
+        };
+        result = get_svg_from_url(http_client.clone(), mirror_url)
+            .await
+            .and_then(|svg_data| check_svg(mirror_url, svg_data))
+            .map_err(|mirror_err| format_err!("{err} (mirror {mirror_url}: {mirror_err})"));
+
+        // Write the logo obtained from the mirror to the cache
+        if let Ok(svg_data) = &result {
+            let mirror_logo = MirrorLogo {
+                generated_at: Utc::now(),
+                mirror_url: mirror_url.clone(),
+                svg_data: STANDARD.encode(svg_data),
+            };
+            cache.write(&mirror_cache_file, &serde_json::to_vec(&mirror_logo)?).await?;
+        }
+    }
+
+    // Look for some issues that may affect the logo portability
+    let svg_data = result?;
+    let issues = find_svg_issues(&svg_data);
+
+    let mut logo = process_svg(cache, svg_data).await?;
+    logo.issues = issues;
+    Ok(logo)
+}
+
+/// Get the logo obtained from one of the mirrors provided in a previous build
+/// from the cache, as long as it hasn't expired yet.
+async fn get_cached_mirror_logo(
+    cache: &DynCache,
+    cache_file: &str,
+    item_id: Uuid,
+    mirrors: &[String],
+) -> Option<Vec<u8>> {
+    if mirrors.is_empty() {
+        return None;
+    }
+    let json_data = cache.read(cache_file).await.ok()??;
+    let mirror_logo: MirrorLogo = serde_json::from_slice(&json_data).ok()?;
+    if !mirrors.contains(&mirror_logo.mirror_url)
+        || !refresh::is_cached_data_valid(
+            &item_id.to_string(),
+            mirror_logo.generated_at,
+            LOGOS_MIRRORS_CACHE_TTL,
+        )
+    {
+        return None;
+    }
+    STANDARD.decode(mirror_logo.svg_data).ok()
+}
+
+/// Find some issues in the SVG logo provided that may affect its portability,
+/// like references to external resources (which break offline rendering) or
+/// large embedded raster images (which bloat the logo file).
+fn find_svg_issues(svg_data: &[u8]) -> Vec<String> {
+    let mut issues = vec![];
+    for image in SVG_IMAGE.find_iter(svg_data) {
+        let Some(c) = SVG_HREF.captures(image.as_bytes()) else {
+            continue;
+        };
+        let href = String::from_utf8_lossy(&c["href"]);
+        if href.starts_with("data:") {
+            if href.len() > SVG_EMBEDDED_RASTER_MAX_SIZE {
+                issues.push(format!(
+                    "logo embeds a large raster image ({} bytes, max: {SVG_EMBEDDED_RASTER_MAX_SIZE})",
+                    href.len()
+                ));
+            }
+        } else if !href.starts_with('#') {
+            issues.push(format!("logo references an external resource: {href}"));
+        }
+    }
+    issues
+}
+
+/// Check the SVG logo provided is not a raster image (a common mistake is to
+/// use a PNG file with an SVG extension) or a Git LFS pointer file (when the
+/// LFS objects have not been fetched).
+fn check_svg(name: &str, svg_data: Vec<u8>) -> Result<Vec<u8>> {
+    if svg_data.starts_with(GIT_LFS_POINTER_HEADER) {
+        return Err(format_err!(
+            "logo {name} is a Git LFS pointer file, please make sure the LFS objects have been fetched (git lfs pull)"
+        ));
+    }
+    if let Some(format) = detect_raster_format(&svg_data) {
+        return Err(format_err!(
+            "logo {name} is a {format} image, but an SVG image is expected"
+        ));
+    }
+    Ok(svg_data)
+}
+
+/// Prepare a logo from the favicon of the site provided. Raster favicons are
+/// embedded in an SVG image, so that they can be processed like any other
+/// logo.
+pub(crate) async fn prepare_favicon_logo(
+    cache: &DynCache,
+    http_client: reqwest::Client,
+    homepage_url: &str,
+) -> Result<Logo> {
+    // Get favicon from the site provided
+    let favicon = get_favicon(http_client, homepage_url).await;
+    metrics::record_logo_fetch(favicon.is_ok());
+    let favicon = favicon?;
+
+    // Embed favicon in an SVG image if needed
+    let svg_data = if let Some(format) = detect_raster_format(&favicon) {
+        let mime_type = if format == "ICO" {
+            "image/x-icon".to_string()
+        } else {
+            format!("image/{}", format.to_lowercase())
+        };
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 100"><image width="100" height="100" href="data:{mime_type};base64,{}"/></svg>"#,
+            STANDARD.encode(&favicon)
+        )
+        .into_bytes()
+    } else if SVG_TAG.is_match(&favicon) {
+        favicon
+    } else {
+        return Err(format_err!("unsupported favicon format"));
+    };
 
+    process_svg(cache, svg_data).await
+}
+
+/// Apply some modifications to the SVG logo provided.
+async fn process_svg(cache: &DynCache, mut svg_data: Vec<u8>) -> Result<Logo> {
     // Remove title if present (some identical logos are using a different
     // title, so we do this before computing the digest)
     svg_data = SVG_TITLE.replace(&svg_data, b"").into_owned();
@@ -49,10 +259,13 @@ pub(crate) async fn prepare_logo(
     // terms of CPU usage, so once we've done it once for a given logo we cache
     // it and try to reuse it).
     let logo_cache_file = format!("logo_{digest}.svg");
-    if let Ok(Some(cached_svg_data)) = cache.read(&logo_cache_file) {
+    if let Ok(Some(cached_svg_data)) = cache.read(&logo_cache_file).await {
+        let dominant_color = get_dominant_color(cache, &digest, &cached_svg_data).await?;
         return Ok(Logo {
             svg_data: cached_svg_data,
             digest,
+            dominant_color,
+            ..Default::default()
         });
     }
 
@@ -70,9 +283,68 @@ pub(crate) async fn prepare_logo(
     }
 
     // Write SVG data to cache
-    cache.write(&logo_cache_file, &svg_data)?;
+    cache.write(&logo_cache_file, &svg_data).await?;
+
+    let dominant_color = get_dominant_color(cache, &digest, &svg_data).await?;
+    Ok(Logo {
+        svg_data,
+        digest,
+        dominant_color,
+        ..Default::default()
+    })
+}
+
+/// Get the dominant color of the SVG logo provided. Rasterizing the logo is
+/// expensive, so the color is cached using the logo digest.
+async fn get_dominant_color(cache: &DynCache, digest: &str, svg_data: &[u8]) -> Result<Option<String>> {
+    // Read cached color (if available). An empty value means the color could
+    // not be computed the last time.
+    let color_cache_file = format!("logo_{digest}.color");
+    if let Ok(Some(cached_color)) = cache.read(&color_cache_file).await {
+        let color = String::from_utf8(cached_color)?;
+        return Ok((!color.is_empty()).then_some(color));
+    }
+
+    // Compute color and write it to cache
+    let color = compute_svg_dominant_color(svg_data).ok().flatten();
+    cache.write(&color_cache_file, color.as_deref().unwrap_or_default().as_bytes()).await?;
+
+    Ok(color)
+}
+
+/// Compute the dominant color of the SVG logo provided (as a hex string). The
+/// logo is rasterized to a small sample, and the (alpha weighted) average
+/// color of its pixels is returned.
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+fn compute_svg_dominant_color(svg_data: &[u8]) -> Result<Option<String>> {
+    // Rasterize logo
+    let tree = usvg::Tree::from_data(svg_data, &usvg::Options::default())?;
+    let size = tree.size;
+    let Some(mut pixmap) = Pixmap::new(DOMINANT_COLOR_SAMPLE_SIZE, DOMINANT_COLOR_SAMPLE_SIZE) else {
+        return Ok(None);
+    };
+    let scale = DOMINANT_COLOR_SAMPLE_SIZE as f32 / size.width().max(size.height());
+    resvg::Tree::from_usvg(&tree).render(Transform::from_scale(scale, scale), &mut pixmap.as_mut());
 
-    Ok(Logo { svg_data, digest })
+    // Calculate average color (pixels are premultiplied by alpha)
+    let (mut red, mut green, mut blue, mut alpha) = (0_u64, 0_u64, 0_u64, 0_u64);
+    for pixel in pixmap.pixels() {
+        red += u64::from(pixel.red());
+        green += u64::from(pixel.green());
+        blue += u64::from(pixel.blue());
+        alpha += u64::from(pixel.alpha());
+    }
+    if alpha == 0 {
+        return Ok(None);
+    }
+    let channel = |value: u64| (value * 255 / alpha).min(255);
+
+    Ok(Some(format!(
+        "#{:02x}{:02x}{:02x}",
+        channel(red),
+        channel(green),
+        channel(blue)
+    )))
 }
 
 /// Get SVG logo content from the corresponding source.
@@ -91,19 +363,167 @@ async fn get_svg(
     if let Some(logos_url) = &logos_source.logos_url {
         let logos_url = logos_url.trim_end_matches('/');
         let logo_url = format!("{logos_url}/{file_name}");
-        let resp = http_client.get(logo_url).send().await?;
-        if resp.status() != StatusCode::OK {
-            return Err(format_err!(
-                "unexpected status code getting logo: {}",
-                resp.status()
-            ));
-        }
-        return Ok(resp.bytes().await?.to_vec());
+        let result = get_svg_from_url(http_client, &logo_url).await;
+        metrics::record_logo_fetch(result.is_ok());
+        return result;
     };
 
     Err(format_err!("logos path or url not provided"))
 }
 
+/// Acquire a permit to send a request to the host of the url provided, so
+/// that hosts serving lots of logos are not overwhelmed. The permit is
+/// released when dropped. No permit is needed if the url has no host.
+async fn acquire_host_permit(url: &str) -> Option<OwnedSemaphorePermit> {
+    let host = Url::parse(url).ok()?.host_str()?.to_lowercase();
+    let semaphore = HOSTS_SEMAPHORES
+        .lock()
+        .expect("hosts semaphores lock not to be poisoned")
+        .entry(host)
+        .or_insert_with(|| Arc::new(Semaphore::new(LOGOS_HOST_MAX_CONCURRENCY)))
+        .clone();
+    semaphore.acquire_owned().await.ok()
+}
+
+/// Get SVG logo from the url provided.
+async fn get_svg_from_url(http_client: reqwest::Client, logo_url: &str) -> Result<Vec<u8>> {
+    let _permit = acquire_host_permit(logo_url).await;
+    let resp = http_client.get(logo_url).send().await?;
+    if resp.status() != StatusCode::OK {
+        return Err(format_err!(
+            "unexpected status code getting logo: {}",
+            resp.status()
+        ));
+    }
+    Ok(resp.bytes().await?.to_vec())
+}
+
+/// Get the favicon of the site provided. The site's homepage is checked for
+/// icons links (SVG icons and apple touch icons are preferred as they usually
+/// look better), falling back to the default favicon location.
+async fn get_favicon(http_client: reqwest::Client, homepage_url: &str) -> Result<Vec<u8>> {
+    let homepage_url = Url::parse(homepage_url)?;
+
+    // Collect favicon candidates urls from the homepage links
+    let mut candidates: Vec<(usize, Url)> = vec![];
+    let permit = acquire_host_permit(homepage_url.as_str()).await;
+    let resp = http_client.get(homepage_url.clone()).send().await?;
+    if resp.status() == StatusCode::OK {
+        let html = resp.text().await?;
+        for link in HTML_LINK.find_iter(&html) {
+            let (mut rel, mut href, mut kind) = ("", "", "");
+            for attr in HTML_ATTR.captures_iter(link.as_str()) {
+                match attr["name"].to_lowercase().as_str() {
+                    "rel" => rel = attr.name("value").map_or("", |v| v.as_str()),
+                    "href" => href = attr.name("value").map_or("", |v| v.as_str()),
+                    "type" => kind = attr.name("value").map_or("", |v| v.as_str()),
+                    _ => {}
+                }
+            }
+            let rel = rel.to_lowercase();
+            let priority = if rel.split_whitespace().any(|r| r == "icon")
+                && (kind.contains("svg")
+                    || Path::new(href).extension().is_some_and(|ext| ext.eq_ignore_ascii_case("svg")))
+            {
+                0
+            } else if rel.starts_with("apple-touch-icon") {
+                1
+            } else if rel.split_whitespace().any(|r| r == "icon") {
+                2
+            } else {
+                continue;
+            };
+            if let Ok(url) = homepage_url.join(href) {
+                candidates.push((priority, url));
+            }
+        }
+    }
+    drop(permit);
+    candidates.sort_by_key(|(priority, _)| *priority);
+    candidates.push((usize::MAX, homepage_url.join("/favicon.ico")?));
+
+    // Return the first candidate available
+    for (_, url) in candidates {
+        let _permit = acquire_host_permit(url.as_str()).await;
+        let Ok(resp) = http_client.get(url).send().await else {
+            continue;
+        };
+        if resp.status() == StatusCode::OK {
+            return Ok(resp.bytes().await?.to_vec());
+        }
+    }
+
+    Err(format_err!("favicon not found"))
+}
+
+/// Detect the format of the image provided if it is a raster image, based on
+/// its magic bytes.
+pub(crate) fn detect_raster_format(data: &[u8]) -> Option<&'static str> {
+    let signatures: [(&[u8], &str); 8] = [
+        (b"\x89PNG\r\n\x1a\n", "PNG"),
+        (b"\xff\xd8\xff", "JPEG"),
+        (b"GIF87a", "GIF"),
+        (b"GIF89a", "GIF"),
+        (b"BM", "BMP"),
+        (b"\x00\x00\x01\x00", "ICO"),
+        (b"II*\x00", "TIFF"),
+        (b"MM\x00*", "TIFF"),
+    ];
+    for (signature, format) in signatures {
+        if data.starts_with(signature) {
+            return Some(format);
+        }
+    }
+    if data.len() >= 12 && &data[0..4] == b"RIFF" && &data[8..12] == b"WEBP" {
+        return Some("WEBP");
+    }
+
+    None
+}
+
+/// Get the size (width and height) of the image provided. Raster images sizes
+/// are in pixels, whereas SVG images sizes are in user units.
+#[allow(clippy::cast_precision_loss)]
+pub(crate) fn get_image_size(data: &[u8]) -> Result<(f32, f32)> {
+    if detect_raster_format(data).is_some() {
+        let size =
+            imagesize::blob_size(data).map_err(|err| format_err!("error getting image size: {err}"))?;
+        return Ok((size.width as f32, size.height as f32));
+    }
+    let tree = usvg::Tree::from_data(data, &usvg::Options::default())?;
+    Ok((tree.size.width(), tree.size.height()))
+}
+
+/// Resize the PNG image provided to the height given (keeping its aspect
+/// ratio), returning the resized PNG image.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+pub(crate) fn resize_png(data: &[u8], height: u32) -> Result<Vec<u8>> {
+    let src = Pixmap::decode_png(data)?;
+    let scale = height as f32 / src.height() as f32;
+    let width = ((src.width() as f32 * scale).round() as u32).max(1);
+    let Some(mut dst) = Pixmap::new(width, height) else {
+        return Err(format_err!("invalid image size: {width}x{height}"));
+    };
+    let paint = PixmapPaint {
+        quality: FilterQuality::Bicubic,
+        ..Default::default()
+    };
+    dst.draw_pixmap(
+        0,
+        0,
+        src.as_ref(),
+        &paint,
+        Transform::from_scale(scale, scale),
+        None,
+    );
+
+    Ok(dst.encode_png()?)
+}
+
 /// Get SVG bounding box (smallest rectangle in which the object fits).
 fn get_svg_bounding_box(svg_data: &[u8]) -> Result<Option<Rect>> {
     let opt = usvg::Options::default();
@@ -0,0 +1,184 @@
+//! This module provides a mechanism to collect some metrics while building a
+//! landscape, so that they can be written to a file in the Prometheus text
+//! exposition format (i.e. to be picked up by the node exporter textfile
+//! collector).
+
+use anyhow::Result;
+use chrono::Utc;
+use lazy_static::lazy_static;
+use std::{collections::BTreeMap, fmt::Write, fs, path::Path, sync::Mutex, time::Duration};
+
+lazy_static! {
+    /// Metrics collected so far.
+    static ref METRICS: Mutex<Metrics> = Mutex::new(Metrics::default());
+}
+
+/// Metrics collected while building the landscape.
+#[derive(Debug, Default)]
+struct Metrics {
+    items: usize,
+    logos_fetched: Counter,
+    phases: Vec<(&'static str, Duration)>,
+    external_data_fetches: BTreeMap<&'static str, Counter>,
+}
+
+/// Number of operations performed, and how many of them failed.
+#[derive(Debug, Default, Clone, Copy)]
+struct Counter {
+    total: u64,
+    failures: u64,
+}
+
+impl Counter {
+    /// Record an operation.
+    fn record(&mut self, success: bool) {
+        self.total += 1;
+        if !success {
+            self.failures += 1;
+        }
+    }
+}
+
+/// Reset the metrics collected so far (i.e. before rebuilding the landscape).
+pub(crate) fn reset() {
+    *METRICS.lock().expect("metrics lock not to be poisoned") = Metrics::default();
+}
+
+/// Set the number of items in the landscape.
+pub(crate) fn set_items_count(items: usize) {
+    METRICS.lock().expect("metrics lock not to be poisoned").items = items;
+}
+
+/// Record a logo fetched from a remote location.
+pub(crate) fn record_logo_fetch(success: bool) {
+    METRICS.lock().expect("metrics lock not to be poisoned").logos_fetched.record(success);
+}
+
+/// Record the duration of a build phase.
+pub(crate) fn record_phase_duration(phase: &'static str, duration: Duration) {
+    METRICS.lock().expect("metrics lock not to be poisoned").phases.push((phase, duration));
+}
+
+/// Record an entity (i.e. a repository) whose data was fetched from the
+/// external service provided (cached data reused is not recorded).
+pub(crate) fn record_external_data_fetch(service: &'static str, success: bool) {
+    METRICS
+        .lock()
+        .expect("metrics lock not to be poisoned")
+        .external_data_fetches
+        .entry(service)
+        .or_default()
+        .record(success);
+}
+
+/// Write the metrics collected so far to the file provided using the
+/// Prometheus text exposition format. The file is written atomically, so that
+/// collectors never read a partially written file.
+pub(crate) fn write_file(path: &Path, success: bool, duration: Duration) -> Result<()> {
+    let metrics = METRICS.lock().expect("metrics lock not to be poisoned");
+    let mut txt = String::new();
+
+    // Gauges with a single sample
+    let gauges = [
+        (
+            "landscape2_build_success",
+            "Whether the last build succeeded.",
+            u8::from(success).to_string(),
+        ),
+        (
+            "landscape2_build_timestamp_seconds",
+            "Time the last build finished (unix timestamp).",
+            Utc::now().timestamp().to_string(),
+        ),
+        (
+            "landscape2_build_duration_seconds",
+            "Time taken by the last build.",
+            format!("{:.3}", duration.as_secs_f64()),
+        ),
+        (
+            "landscape2_build_items",
+            "Number of items in the landscape.",
+            metrics.items.to_string(),
+        ),
+    ];
+    for (name, help, value) in gauges {
+        write_metric(&mut txt, (name, "gauge", help), &[(String::new(), value)]);
+    }
+    write_metric(
+        &mut txt,
+        (
+            "landscape2_build_phase_duration_seconds",
+            "gauge",
+            "Time taken by each of the last build phases.",
+        ),
+        &metrics
+            .phases
+            .iter()
+            .map(|(phase, duration)| {
+                (
+                    format!("{{phase=\"{phase}\"}}"),
+                    format!("{:.3}", duration.as_secs_f64()),
+                )
+            })
+            .collect::<Vec<_>>(),
+    );
+    write_metric(
+        &mut txt,
+        (
+            "landscape2_build_logos_fetched_total",
+            "counter",
+            "Number of logos fetched from remote locations.",
+        ),
+        &[(String::new(), metrics.logos_fetched.total.to_string())],
+    );
+    write_metric(
+        &mut txt,
+        (
+            "landscape2_build_logos_fetch_failures_total",
+            "counter",
+            "Number of logos that could not be fetched from remote locations.",
+        ),
+        &[(String::new(), metrics.logos_fetched.failures.to_string())],
+    );
+    let services_samples = |value: fn(&Counter) -> u64| -> Vec<(String, String)> {
+        metrics
+            .external_data_fetches
+            .iter()
+            .map(|(service, counter)| (format!("{{service=\"{service}\"}}"), value(counter).to_string()))
+            .collect()
+    };
+    write_metric(
+        &mut txt,
+        (
+            "landscape2_build_external_data_fetches_total",
+            "counter",
+            "Number of entities whose data was fetched from external services.",
+        ),
+        &services_samples(|counter| counter.total),
+    );
+    write_metric(
+        &mut txt,
+        (
+            "landscape2_build_external_data_fetch_failures_total",
+            "counter",
+            "Number of entities whose data could not be fetched from external services.",
+        ),
+        &services_samples(|counter| counter.failures),
+    );
+
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, txt)?;
+    fs::rename(&tmp_path, path)?;
+
+    Ok(())
+}
+
+/// Write a metric family (name, type and help) and its samples (labels and
+/// value) to the text provided.
+fn write_metric(txt: &mut String, (name, kind, help): (&str, &str, &str), samples: &[(String, String)]) {
+    _ = writeln!(txt, "# HELP {name} {help}");
+    _ = writeln!(txt, "# TYPE {name} {kind}");
+    for (labels, value) in samples {
+        _ = writeln!(txt, "{name}{labels} {value}");
+    }
+}
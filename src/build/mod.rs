@@ -3,49 +3,86 @@
 #![allow(non_upper_case_globals)]
 
 use self::{
-    cache::Cache,
-    crunchbase::collect_crunchbase_data,
+    bundle::Bundle,
+    cache::{new_cache, DynCache},
+    cratesio::{collect_cratesio_data, CratesIOData},
+    crunchbase::{check_crunchbase_data, collect_crunchbase_data, CrunchbaseData},
+    data::Category,
     datasets::Datasets,
-    export::generate_items_csv,
-    github::collect_github_data,
+    export::{generate_graph, generate_items_csv},
+    github::{
+        check_github_data, collect_github_data, collect_github_orgs_data, get_repositories_host,
+        prune_stars_history, GithubData, GithubOrgsData,
+    },
     guide::LandscapeGuide,
-    logos::prepare_logo,
-    projects::{generate_projects_csv, Project, ProjectsMd},
-    settings::{Images, LandscapeSettings},
+    history::generate_history,
+    logos::{detect_raster_format, get_image_size, prepare_favicon_logo, prepare_logo, resize_png},
+    projects::{generate_projects_csv, render_custom_projects_md, Project, ProjectsMd},
+    scorecard::{collect_scorecard_data, ScorecardData},
+    settings::{Colors, CustomCode, Images, RedirectRule},
+    single_file::{data_uri, inline_datasets_files, inline_web_assets},
+    social::{collect_social_data, SocialData, SocialNetwork},
+    warnings::WarningKind,
 };
-use crate::{BuildArgs, GuideSource, LogosSource};
+use crate::{BuildArgs, GraphFormat, GuideSource, LogosSource};
 use anyhow::{format_err, Context, Result};
 use askama::Template;
+use flate2::{write::GzEncoder, Compression};
 use futures::stream::{self, StreamExt};
+use qrcode::{render::svg, QrCode};
 use reqwest::StatusCode;
 use rust_embed::RustEmbed;
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::Write as _,
     fs::{self, File},
+    future::Future,
     io::Write,
-    path::Path,
+    path::{Path, PathBuf},
+    process::Stdio,
     sync::Arc,
-    time::Instant,
+    time::{Duration, Instant},
 };
-use tracing::{debug, error, info, instrument};
+use tokio::io::AsyncWriteExt;
+use tracing::{debug, error, info, instrument, warn};
 use url::Url;
 use uuid::Uuid;
+use walkdir::WalkDir;
 
+mod a11y;
+mod bundle;
 mod cache;
+mod concurrency;
+mod cratesio;
 mod crunchbase;
 mod data;
 mod datasets;
 mod export;
 mod github;
 mod guide;
+mod history;
 mod logos;
+mod metrics;
+mod preflight;
 mod projects;
+mod refresh;
+mod scorecard;
 mod settings;
+mod single_file;
+mod social;
+pub(crate) mod warnings;
+mod watch;
 pub(crate) use data::LandscapeData;
+pub(crate) use preflight::preflight;
+pub(crate) use settings::LandscapeSettings;
+pub(crate) use watch::watch;
 
 /// Path where the datasets will be written to in the output directory.
 const DATASETS_PATH: &str = "data";
 
+/// Path where some debugging files will be written to in the output directory.
+const DEBUG_PATH: &str = "debug";
+
 /// Path where some documents will be written to in the output directory.
 const DOCS_PATH: &str = "docs";
 
@@ -55,73 +92,474 @@ const IMAGES_PATH: &str = "images";
 /// Path where the item logos will be written to in the output directory.
 const LOGOS_PATH: &str = "logos";
 
+/// File name of the default logo in the LOGOS_PATH.
+const DEFAULT_LOGO_FILE_NAME: &str = "default.svg";
+
+/// Path where the items QR codes will be written to in the output directory.
+const QR_CODES_PATH: &str = "qr";
+
+/// Environment variable containing the output directory path, available to
+/// the post build command.
+const OUTPUT_DIR_ENV_VAR: &str = "LANDSCAPE_OUTPUT_DIR";
+
 /// Maximum number of logos to prepare concurrently.
 const PREPARE_LOGOS_MAX_CONCURRENCY: usize = 20;
 
+/// Height (in CSS pixels) the header logo is displayed at in the web
+/// application.
+const SETTINGS_HEADER_LOGO_HEIGHT: u32 = 48;
+
+/// Height (in CSS pixels) the footer logo is displayed at in the web
+/// application.
+const SETTINGS_FOOTER_LOGO_HEIGHT: u32 = 34;
+
+/// Pixel densities of the variants generated for the header and footer logos.
+const SETTINGS_LOGO_DENSITIES: [u32; 3] = [1, 2, 3];
+
+/// Minimum aspect ratio (width / height) expected for the header and footer
+/// logos.
+const SETTINGS_LOGO_MIN_ASPECT_RATIO: f32 = 1.0;
+
+/// Maximum aspect ratio (width / height) expected for the header and footer
+/// logos.
+const SETTINGS_LOGO_MAX_ASPECT_RATIO: f32 = 10.0;
+
 /// Embed web application assets into binary.
 /// (these assets will be built automatically from the build script)
 #[derive(RustEmbed)]
 #[folder = "web/dist"]
 struct WebAssets;
 
-/// Build landscape website.
+/// Build landscape website, writing the build metrics to the metrics file
+/// when one is provided (even if the build fails). In strict mode, the build
+/// fails if any warnings were collected.
 #[instrument(skip_all)]
-pub(crate) async fn build(args: &BuildArgs) -> Result<()> {
+pub(crate) async fn build(args: &BuildArgs, strict: bool) -> Result<()> {
+    let start = Instant::now();
+    metrics::reset();
+
+    let result = build_landscape(args, strict).await;
+    if let Some(metrics_file) = &args.metrics_file {
+        metrics::write_file(metrics_file, result.is_ok(), start.elapsed())
+            .context("error writing metrics file")?;
+    }
+
+    result
+}
+
+/// Build landscape website.
+#[allow(clippy::too_many_lines)]
+async fn build_landscape(args: &BuildArgs, strict: bool) -> Result<()> {
     info!("building landscape website..");
     let start = Instant::now();
+    let mut timings = Timings::default();
 
-    // Check required web assets are present
-    check_web_assets()?;
+    // Check required web assets are present (not needed if we are only
+    // generating the datasets or the logos)
+    if !args.datasets_only && !args.logos_only {
+        check_web_assets()?;
+    }
 
     // Setup output directory, creating it when needed
     setup_output_dir(&args.output_dir)?;
 
+    // Setup the maximum concurrency for the whole build
+    concurrency::set_max_concurrency(args.max_concurrency)?;
+
+    // Extract landscape bundle if provided and setup the sources
+    let bundle = args.bundle.as_deref().map(Bundle::extract).transpose()?;
+    let data_source = bundle.as_ref().map_or(&args.data_source, |b| &b.data_source);
+    let guide_source = bundle.as_ref().map_or(&args.guide_source, |b| &b.guide_source);
+    let logos_source = bundle.as_ref().map_or(&args.logos_source, |b| &b.logos_source);
+    let settings_source = bundle.as_ref().map_or(&args.settings_source, |b| &b.settings_source);
+
     // Setup cache
-    let cache = Cache::new(&args.cache_dir)?;
+    let cache = new_cache(
+        args.cache_backend,
+        args.cache_dir.as_deref(),
+        args.cache_s3_bucket.as_deref(),
+        args.cache_namespace.as_deref(),
+        args.cache_read_only,
+    )
+    .await?;
 
     // Get landscape data from the source provided
-    let mut landscape_data = LandscapeData::new(&args.data_source).await?;
+    let phase_start = Instant::now();
+    let mut landscape_data = LandscapeData::new(data_source).await?;
+    timings.record("data load", phase_start);
+    metrics::set_items_count(landscape_data.items.len());
+
+    // Check the landscape data contains some items, as an empty landscape is
+    // likely the result of a misconfigured data source
+    if landscape_data.items.is_empty() && !args.allow_empty {
+        return Err(format_err!(
+            "landscape data contains no items (use --allow-empty to build it anyway)"
+        ));
+    }
+
+    // Setup the external data to refresh (only the changed items data will be
+    // refreshed if provided)
+    let github_host = get_repositories_host(args.github_base_url.as_deref())?;
+    refresh::set_changed_items(&landscape_data, args.changed_items.as_deref(), &github_host)?;
+
+    // Only prepare the items logos if requested
+    if args.logos_only {
+        prepare_items_logos(
+            &cache,
+            logos_source,
+            args.favicon_fallback,
+            &args.default_logo,
+            args.logos_shard_length,
+            &mut landscape_data,
+            &args.output_dir,
+        )
+        .await?;
+        if args.prune_output_logos {
+            prune_output_logos(&landscape_data, &args.output_dir)?;
+        }
+        check_output_paths_collisions(&args.output_dir)?;
+        generate_logos_mapping_file(&landscape_data, &args.output_dir)?;
+        generate_shared_logos_report(&landscape_data, &args.output_dir)?;
+
+        let duration = start.elapsed().as_secs_f64();
+        info!("landscape logos prepared! (took: {:.3}s)", duration);
+        return Ok(());
+    }
 
     // Get landscape settings from the source provided
-    let mut settings = LandscapeSettings::new(&args.settings_source).await?;
+    let mut settings = LandscapeSettings::new(settings_source).await?;
+
+    // Check the categories referenced by the items are defined in the settings
+    landscape_data.validate_items_categories(&settings);
+
+    // Check the tags referenced by the items are defined in the settings
+    landscape_data.validate_items_tags(&settings)?;
+
+    // Check the items maturity is one of the levels defined in the settings
+    landscape_data.validate_items_maturity(&settings)?;
 
     // Add some extra information to the landscape based on the settings
     landscape_data.add_featured_items_data(&settings)?;
     landscape_data.add_member_subcategory(&settings.members_category);
 
     // Get settings images and update their urls to the local copy
-    settings.images = get_settings_images(&settings, &args.output_dir).await?;
+    settings.images = get_settings_images(&settings, args.strict_settings_images, &args.output_dir).await?;
 
     // Prepare guide and copy it to the output directory
-    let includes_guide = prepare_guide(&args.guide_source, &args.output_dir).await?.is_some();
+    let guide = prepare_guide(guide_source, &settings, &args.output_dir).await?;
+    let includes_guide = guide.is_some();
+
+    // Check the references in the guide exist in the landscape data
+    if let Some(guide) = &guide {
+        guide.check_references(&landscape_data);
+    }
 
     // Prepare items logos and copy them to the output directory
-    prepare_items_logos(&cache, &args.logos_source, &mut landscape_data, &args.output_dir).await?;
+    let phase_start = Instant::now();
+    prepare_items_logos(
+        &cache,
+        logos_source,
+        args.favicon_fallback,
+        &args.default_logo,
+        args.logos_shard_length,
+        &mut landscape_data,
+        &args.output_dir,
+    )
+    .await?;
+    if args.prune_output_logos {
+        prune_output_logos(&landscape_data, &args.output_dir)?;
+    }
+    check_output_paths_collisions(&args.output_dir)?;
+    generate_shared_logos_report(&landscape_data, &args.output_dir)?;
+    timings.record("logos preparation", phase_start);
 
     // Collect data from external services
-    let (crunchbase_data, github_data) = tokio::try_join!(
-        collect_crunchbase_data(&cache, &landscape_data),
-        collect_github_data(&cache, &landscape_data)
+    let mut social_networks = vec![];
+    if args.bluesky {
+        social_networks.push(SocialNetwork::Bluesky);
+    }
+    if args.mastodon {
+        social_networks.push(SocialNetwork::Mastodon);
+    }
+    let (
+        (cratesio_data, cratesio_duration),
+        (crunchbase_data, crunchbase_duration),
+        (github_data, github_duration),
+        (github_orgs_data, github_orgs_duration),
+        (scorecard_data, scorecard_duration),
+        (social_data, social_duration),
+    ) = tokio::try_join!(
+        timed(collect_cratesio_data(&cache, &landscape_data)),
+        timed(collect_crunchbase_data(&cache, &landscape_data)),
+        timed(collect_github_data(
+            &cache,
+            &landscape_data,
+            &args.github_base_url,
+            &args.github_tokens_file,
+            args.github_activity_days,
+            args.github_commit_activity
+        )),
+        timed(collect_github_orgs_data(
+            &cache,
+            &landscape_data,
+            &args.github_base_url,
+            &args.github_tokens_file
+        )),
+        timed(async {
+            if args.scorecard {
+                collect_scorecard_data(&cache, &landscape_data).await
+            } else {
+                Ok(ScorecardData::default())
+            }
+        }),
+        timed(collect_social_data(&cache, &landscape_data, &social_networks))
     )?;
+    timings.record_duration("crates.io data collection", cratesio_duration);
+    timings.record_duration("crunchbase data collection", crunchbase_duration);
+    timings.record_duration("github data collection", github_duration);
+    timings.record_duration("github orgs data collection", github_orgs_duration);
+    timings.record_duration("scorecard data collection", scorecard_duration);
+    timings.record_duration("social data collection", social_duration);
+
+    // Check the values of the data collected from external services are plausible
+    check_crunchbase_data(&crunchbase_data);
+    check_github_data(&github_data);
+
+    // Dump data collected from external services if requested
+    if args.dump_external_data {
+        dump_external_data(
+            &cratesio_data,
+            &crunchbase_data,
+            &github_data,
+            &github_orgs_data,
+            &scorecard_data,
+            &social_data,
+            &args.output_dir,
+        )?;
+    }
 
     // Add data collected from external services to the landscape data
+    landscape_data.add_cratesio_data(cratesio_data)?;
     landscape_data.add_crunchbase_data(crunchbase_data)?;
+    landscape_data.normalize_organizations_names(&settings.organizations_legal_suffixes);
     landscape_data.add_github_data(github_data)?;
+    landscape_data.add_github_orgs_data(github_orgs_data, &github_host)?;
+    landscape_data.add_scorecard_data(scorecard_data)?;
+    landscape_data.add_social_data(social_data)?;
+
+    // Report items below the minimum stars threshold if requested
+    if let Some(min_stars) = args.min_stars {
+        for (item, stars) in landscape_data.items_below_min_stars(min_stars) {
+            warnings::add(
+                WarningKind::LowStars,
+                Some(&item.name),
+                format!("primary repository has {stars} stars (minimum: {min_stars})"),
+            );
+        }
+    }
+
+    // Transform landscape data using the command provided
+    if let Some(command) = &args.transform {
+        landscape_data = run_transform_command(command, &landscape_data).await?;
+
+        // Check the transformed data in the same way the original data was
+        landscape_data.validate_items()?;
+        landscape_data.validate_items_ids()?;
+        landscape_data.resolve_items_parents()?;
+        landscape_data.validate_items_categories(&settings);
+        landscape_data.validate_items_tags(&settings)?;
+        landscape_data.validate_items_maturity(&settings)?;
+    }
+
+    // Remove items that have reached their end of life if requested
+    if args.hide_eol {
+        landscape_data.remove_eol_items();
+    }
+
+    // Render items descriptions to HTML if requested
+    if args.render_descriptions {
+        let html_sanitization = settings.html_sanitization.clone().unwrap_or_default();
+        landscape_data.render_items_descriptions(&html_sanitization.sanitizer())?;
+    }
+
+    // Sort landscape items if requested
+    if let Some(sort_by) = args.sort_by {
+        landscape_data.sort_items(sort_by);
+    }
+    let keep_order = args.sort_by.is_some();
+
+    // Generate items QR codes if requested
+    if args.generate_qr {
+        if let Some(base_href) = &args.base_href {
+            generate_items_qr_codes(base_href, &mut landscape_data, &args.output_dir)?;
+        }
+    }
 
     // Generate datasets for web application
-    let datasets = generate_datasets(&landscape_data, &settings, includes_guide, &args.output_dir)?;
+    let phase_start = Instant::now();
+    let datasets = generate_datasets(
+        &landscape_data,
+        &settings,
+        includes_guide,
+        args.assets_base_url.as_deref(),
+        args.split_by_category,
+        args.pretty_datasets,
+        args.shard_full,
+        &args.output_dir,
+    )?;
+    timings.record("datasets generation", phase_start);
+
+    // Generate landscape history from the data file git repository if requested
+    if args.history {
+        if let Some(data_file) = &data_source.data_file {
+            let phase_start = Instant::now();
+            generate_history(data_file, &args.output_dir).await?;
+            timings.record("history generation", phase_start);
+        }
+    }
 
-    // Render index file and write it to the output directory
-    render_index(&datasets, &args.output_dir)?;
+    if !args.datasets_only {
+        let phase_start = Instant::now();
+        let json_ld = args.json_ld.then(|| generate_json_ld(&datasets, args.base_href.as_deref()));
+        if args.single_file {
+            // Render a self-contained index file and write it to the output
+            // directory (web assets are inlined in it)
+            render_single_file_index(
+                &datasets,
+                guide.as_ref(),
+                settings.custom_code.as_ref(),
+                json_ld.as_ref(),
+                &args.output_dir,
+            )?;
+        } else {
+            // Render index file and write it to the output directory
+            let embedded_guide = guide.as_ref().filter(|_| args.embed_guide);
+            render_index(
+                &datasets,
+                embedded_guide,
+                settings.custom_code.as_ref(),
+                json_ld.as_ref(),
+                &args.output_dir,
+            )?;
+
+            // Copy web assets files to the output directory
+            copy_web_assets(&args.output_dir)?;
+        }
+        timings.record("index rendering and web assets copy", phase_start);
 
-    // Copy web assets files to the output directory
-    copy_web_assets(&args.output_dir)?;
+        // Check the index document accessibility if requested
+        if args.a11y_check {
+            a11y::check_index_accessibility(&args.output_dir)?;
+        }
 
-    // Generate items.csv file
-    generate_items_csv_file(&landscape_data, &args.output_dir)?;
+        // Generate items.csv file
+        generate_items_csv_file(&landscape_data, keep_order, &args.output_dir)?;
 
-    // Generate projects.* files
-    generate_projects_files(&landscape_data, &args.output_dir)?;
+        // Generate projects.* files
+        generate_projects_files(
+            &landscape_data,
+            keep_order,
+            &args.projects_template,
+            &args.output_dir,
+        )?;
+
+        // Generate categories markdown files if requested
+        if args.categories_md {
+            generate_categories_md_files(&landscape_data, keep_order, &args.output_dir)?;
+        }
+    }
+
+    // Generate landscape graph file if requested
+    if let Some(format) = args.export_graph {
+        generate_graph_file(&landscape_data, format, &args.output_dir)?;
+    }
+
+    if !args.datasets_only {
+        // Generate hosting files (_redirects, _headers) if required
+        generate_hosting_files(&settings, &args.output_dir)?;
+
+        // Generate robots.txt file
+        generate_robots_file(&settings, &args.output_dir)?;
+
+        // Generate error page (404.html)
+        generate_error_page(
+            &settings,
+            &args.error_page,
+            args.base_href.as_deref(),
+            &args.output_dir,
+        )?;
+
+        // Generate embeddable widget if requested
+        if let Some(category) = &args.widget_category {
+            generate_widget(
+                &settings,
+                &datasets,
+                category,
+                args.base_href.as_deref(),
+                &args.output_dir,
+            )?;
+        }
+
+        // Generate llms.txt file if requested
+        if args.llms_txt {
+            generate_llms_txt_file(&settings, &landscape_data, &args.output_dir)?;
+        }
+
+        // Generate feeds.opml file if requested
+        if args.feeds_opml {
+            generate_feeds_opml_file(&settings, &landscape_data, &args.output_dir)?;
+        }
+    }
+
+    // Write warnings summary files to the output directory (before checking
+    // or packaging it, so that they are included)
+    warnings::write_summary_files(&args.output_dir)?;
+
+    // Verify the output directory contents if requested
+    if args.verify_output {
+        verify_output(
+            &datasets,
+            args.datasets_only,
+            args.assets_base_url.as_deref(),
+            &args.output_dir,
+        )?;
+    }
+
+    // Check the output directory size if a maximum has been provided
+    if let Some(max_output_size) = args.max_output_size {
+        check_output_size(max_output_size, &args.output_dir)?;
+    }
+
+    // Package output directory into an archive if requested
+    if args.archive {
+        archive_output_dir(&args.output_dir)?;
+    }
+
+    // Prune cache if requested (entries cannot be removed from a read-only
+    // cache, so pruning is skipped in that case)
+    if args.prune_cache {
+        if args.cache_read_only {
+            warn!("cache is read-only: skipping cache pruning");
+        } else {
+            prune_cache(&cache, &landscape_data).await?;
+        }
+    }
+
+    // Fail the build in strict mode if any warnings were collected, so that
+    // the post build command is not run on a build that is about to fail
+    if strict {
+        warnings::report(strict)?;
+    }
+
+    // Run post build command if provided
+    if let Some(command) = &args.post_build_command {
+        run_post_build_command(command, &args.output_dir).await?;
+    }
+
+    // Report build phases timings if requested
+    if args.timings {
+        timings.report();
+    }
 
     let duration = start.elapsed().as_secs_f64();
     info!("landscape website built! (took: {:.3}s)", duration);
@@ -129,6 +567,100 @@ pub(crate) async fn build(args: &BuildArgs) -> Result<()> {
     Ok(())
 }
 
+/// Time taken by some of the build phases.
+#[derive(Debug, Default)]
+struct Timings {
+    phases: Vec<(&'static str, Duration)>,
+}
+
+impl Timings {
+    /// Record the time elapsed since the phase provided started.
+    fn record(&mut self, phase: &'static str, phase_start: Instant) {
+        self.record_duration(phase, phase_start.elapsed());
+    }
+
+    /// Record the duration of the phase provided.
+    fn record_duration(&mut self, phase: &'static str, duration: Duration) {
+        metrics::record_phase_duration(phase, duration);
+        self.phases.push((phase, duration));
+    }
+
+    /// Report the time taken by each of the phases recorded.
+    fn report(&self) {
+        for (phase, duration) in &self.phases {
+            info!("{phase} took: {:.3}s", duration.as_secs_f64());
+        }
+    }
+}
+
+/// Await the future provided, returning its output along with the time it
+/// took to complete.
+async fn timed<T>(future: impl Future<Output = Result<T>>) -> Result<(T, Duration)> {
+    let start = Instant::now();
+    let output = future.await?;
+    Ok((output, start.elapsed()))
+}
+
+/// Package the output directory into a .tar.gz archive written next to it.
+/// The archive entries are relative to the output directory, so that it can
+/// be unpacked directly on the web host.
+#[instrument(skip_all, err)]
+fn archive_output_dir(output_dir: &Path) -> Result<()> {
+    debug!("archiving output directory");
+
+    let output_dir = fs::canonicalize(output_dir)?;
+    let Some(dir_name) = output_dir.file_name() else {
+        return Err(format_err!("invalid output directory: {}", output_dir.display()));
+    };
+    let archive_path = output_dir.with_file_name(format!("{}.tar.gz", dir_name.to_string_lossy()));
+
+    let encoder = GzEncoder::new(File::create(&archive_path)?, Compression::default());
+    let mut archive = tar::Builder::new(encoder);
+    archive.append_dir_all(".", &output_dir)?;
+    archive.into_inner()?.finish()?;
+
+    info!(archive = ?archive_path, "output directory archived");
+    Ok(())
+}
+
+/// Check the total size of the output directory does not exceed the maximum
+/// provided. When it does, the size of each of the entries in the output
+/// directory (i.e. logos, data, images) is reported so that users know what to
+/// trim.
+#[instrument(skip_all, err)]
+fn check_output_size(max_output_size: u64, output_dir: &Path) -> Result<()> {
+    debug!("checking output directory size");
+
+    // Calculate the size of each top level entry in the output directory
+    let mut sizes: BTreeMap<String, u64> = BTreeMap::new();
+    for entry in WalkDir::new(output_dir).min_depth(1) {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let path = entry.path().strip_prefix(output_dir)?;
+        let top_level_entry = path
+            .components()
+            .next()
+            .map_or_else(String::new, |c| c.as_os_str().to_string_lossy().to_string());
+        *sizes.entry(top_level_entry).or_default() += entry.metadata()?.len();
+    }
+    let total_size: u64 = sizes.values().sum();
+
+    if total_size > max_output_size {
+        let mut breakdown = String::new();
+        for (entry, size) in &sizes {
+            _ = writeln!(breakdown, "- {entry}: {size} bytes");
+        }
+        return Err(format_err!(
+            "output directory size ({total_size} bytes) exceeds the maximum allowed ({max_output_size} bytes):\n{}",
+            breakdown.trim_end()
+        ));
+    }
+
+    Ok(())
+}
+
 /// Check web assets are present, to make sure the web app has been built.
 #[instrument(skip_all, err)]
 fn check_web_assets() -> Result<()> {
@@ -167,42 +699,340 @@ fn copy_web_assets(output_dir: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Generate a QR code (SVG) for each item, encoding the url of the item's page
+/// in the landscape website, and write them to the QR_CODES_PATH in the output
+/// directory. Items are updated to reference their QR code.
+#[instrument(skip_all, err)]
+fn generate_items_qr_codes(
+    base_href: &str,
+    landscape_data: &mut LandscapeData,
+    output_dir: &Path,
+) -> Result<()> {
+    debug!("generating items qr codes");
+
+    let Ok(base_url) = Url::parse(base_href) else {
+        return Err(format_err!("invalid base href: {base_href}"));
+    };
+    let qr_codes_path = output_dir.join(QR_CODES_PATH);
+    if !qr_codes_path.exists() {
+        fs::create_dir(&qr_codes_path)?;
+    }
+
+    for item in &mut landscape_data.items {
+        let mut item_url = base_url.clone();
+        item_url.query_pairs_mut().append_pair("item", &item.id.to_string());
+        let Ok(qr_code) = QrCode::new(item_url.as_str()) else {
+            warn!(?item.name, "error generating qr code, skipping item");
+            continue;
+        };
+        let svg_data = qr_code.render::<svg::Color>().min_dimensions(200, 200).build();
+        let file_name = format!("{}.svg", item.id);
+        File::create(qr_codes_path.join(&file_name))?.write_all(svg_data.as_bytes())?;
+        item.qr_code = Some(format!("{QR_CODES_PATH}/{file_name}"));
+    }
+
+    Ok(())
+}
+
+/// Write the raw data collected from external services to the DEBUG_PATH in the
+/// output directory, so that it can be inspected when something looks wrong.
+#[instrument(skip_all, err)]
+#[allow(clippy::too_many_arguments)]
+fn dump_external_data(
+    cratesio_data: &CratesIOData,
+    crunchbase_data: &CrunchbaseData,
+    github_data: &GithubData,
+    github_orgs_data: &GithubOrgsData,
+    scorecard_data: &ScorecardData,
+    social_data: &SocialData,
+    output_dir: &Path,
+) -> Result<()> {
+    debug!("dumping external data");
+
+    let debug_path = output_dir.join(DEBUG_PATH);
+    if !debug_path.exists() {
+        fs::create_dir(&debug_path)?;
+    }
+
+    File::create(debug_path.join("cratesio_data.json"))?
+        .write_all(&serde_json::to_vec_pretty(cratesio_data)?)?;
+    File::create(debug_path.join("crunchbase_data.json"))?
+        .write_all(&serde_json::to_vec_pretty(crunchbase_data)?)?;
+    File::create(debug_path.join("github_data.json"))?.write_all(&serde_json::to_vec_pretty(github_data)?)?;
+    File::create(debug_path.join("github_orgs_data.json"))?
+        .write_all(&serde_json::to_vec_pretty(github_orgs_data)?)?;
+    File::create(debug_path.join("scorecard_data.json"))?
+        .write_all(&serde_json::to_vec_pretty(scorecard_data)?)?;
+    File::create(debug_path.join("social_data.json"))?.write_all(&serde_json::to_vec_pretty(social_data)?)?;
+
+    Ok(())
+}
+
 /// Generate datasets from the landscape data and settings, as well as from the
 /// data collected from external services (GitHub, Crunchbase, etc). Some of
 /// the datasets will be embedded in the index document, and the rest will be
-/// written to the DATASETS_PATH in the output directory.
+/// written to the DATASETS_PATH in the output directory. When requested, the
+/// full dataset will also be split into a file per category.
+#[allow(clippy::too_many_arguments)]
 #[instrument(skip_all, err)]
 fn generate_datasets(
     landscape_data: &LandscapeData,
     settings: &LandscapeSettings,
     includes_guide: bool,
+    assets_base_url: Option<&str>,
+    split_by_category: bool,
+    pretty: bool,
+    shard_full: Option<usize>,
     output_dir: &Path,
 ) -> Result<Datasets> {
     debug!("generating datasets");
 
-    let datasets = Datasets::new(landscape_data, settings, includes_guide)?;
+    let mut datasets = Datasets::new(landscape_data, settings, includes_guide)?;
+    if let Some(assets_base_url) = assets_base_url {
+        datasets.rewrite_assets_urls(assets_base_url);
+    }
     let datasets_path = output_dir.join(DATASETS_PATH);
 
     // Base
     let mut base_file = File::create(datasets_path.join("base.json"))?;
-    base_file.write_all(&serde_json::to_vec(&datasets.base)?)?;
+    base_file.write_all(&dataset_to_json(&datasets.base, pretty)?)?;
 
     // Full
     let mut full_file = File::create(datasets_path.join("full.json"))?;
-    full_file.write_all(&serde_json::to_vec(&datasets.full)?)?;
+    full_file.write_all(&dataset_to_json(&datasets.full, pretty)?)?;
+
+    // Full shards
+    if let Some(count) = shard_full {
+        let mut index = FullShardsIndex {
+            items: datasets.full.items.len(),
+            shards: vec![],
+        };
+        for (i, shard) in datasets.full.shards(count).iter().enumerate() {
+            let file_name = format!("full-{i}.json");
+            let mut shard_file = File::create(datasets_path.join(&file_name))?;
+            shard_file.write_all(&dataset_to_json(shard, pretty)?)?;
+            index.shards.push(file_name);
+        }
+        let mut index_file = File::create(datasets_path.join("full-index.json"))?;
+        index_file.write_all(&dataset_to_json(&index, pretty)?)?;
+    }
+
+    // Categories
+    if split_by_category {
+        let categories_path = datasets_path.join("categories");
+        if !categories_path.exists() {
+            fs::create_dir(&categories_path)?;
+        }
+        let slugs = categories_slugs(&landscape_data.categories);
+        for category in &landscape_data.categories {
+            let category_dataset = datasets.full.for_category(&category.name);
+            let file_name = format!("{}.json", slugs[category.name.as_str()]);
+            let mut category_file = File::create(categories_path.join(file_name))?;
+            category_file.write_all(&dataset_to_json(&category_dataset, pretty)?)?;
+        }
+    }
 
     Ok(datasets)
 }
 
+/// Index of the full dataset shards.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+struct FullShardsIndex {
+    items: usize,
+    shards: Vec<String>,
+}
+
+/// Serialize the dataset provided to JSON (pretty-printed if requested).
+fn dataset_to_json<T: serde::Serialize>(dataset: &T, pretty: bool) -> Result<Vec<u8>> {
+    if pretty {
+        Ok(serde_json::to_vec_pretty(dataset)?)
+    } else {
+        Ok(serde_json::to_vec(dataset)?)
+    }
+}
+
+/// Convert the name provided into a slug suitable to be used as a file name
+/// (i.e. "App Definition and Development" -> "app-definition-and-development").
+fn slugify(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<&str>>()
+        .join("-")
+}
+
+/// Get the slugs of the categories provided, suitable to be used as file names.
+/// When a slug is empty (i.e. the name has no ascii alphanumeric characters) or
+/// it has already been used by another category, a numeric suffix is appended
+/// to it so that each category gets its own file.
+fn categories_slugs(categories: &[Category]) -> HashMap<&str, String> {
+    let mut slugs = HashMap::new();
+    let mut used = HashSet::new();
+    for category in categories {
+        let mut slug = slugify(&category.name);
+        if slug.is_empty() {
+            slug = "category".to_string();
+        }
+        let mut id = slug.clone();
+        let mut count = 0;
+        while !used.insert(id.clone()) {
+            count += 1;
+            id = format!("{slug}-{count}");
+        }
+        slugs.insert(category.name.as_str(), id);
+    }
+    slugs
+}
+
+/// Generate the hosting files (_redirects and _headers) from the hosting rules
+/// defined in the settings. When no redirect rules are provided, a catch-all
+/// rule to the index document is used so that the web app routes work.
+#[instrument(skip_all, err)]
+fn generate_hosting_files(settings: &LandscapeSettings, output_dir: &Path) -> Result<()> {
+    let Some(hosting_rules) = &settings.hosting_rules else {
+        return Ok(());
+    };
+
+    debug!("generating hosting files");
+
+    // _redirects
+    let default_redirects = vec![RedirectRule {
+        from: "/*".to_string(),
+        to: "/index.html".to_string(),
+        status: Some(200),
+    }];
+    let mut redirects = String::new();
+    for rule in hosting_rules.redirects.as_ref().unwrap_or(&default_redirects) {
+        _ = write!(redirects, "{} {}", rule.from, rule.to);
+        if let Some(status) = rule.status {
+            _ = write!(redirects, " {status}");
+        }
+        redirects.push('\n');
+    }
+    File::create(output_dir.join("_redirects"))?.write_all(redirects.as_bytes())?;
+
+    // _headers
+    if let Some(headers_rules) = &hosting_rules.headers {
+        let mut headers = String::new();
+        for rule in headers_rules {
+            _ = writeln!(headers, "{}", rule.path);
+            for (name, value) in &rule.headers {
+                _ = writeln!(headers, "  {name}: {value}");
+            }
+        }
+        File::create(output_dir.join("_headers"))?.write_all(headers.as_bytes())?;
+    }
+
+    Ok(())
+}
+
+/// Generate the robots.txt file from the robots rules in the settings. When no
+/// rules are provided, crawlers will be allowed to access everything.
+#[instrument(skip_all, err)]
+fn generate_robots_file(settings: &LandscapeSettings, output_dir: &Path) -> Result<()> {
+    debug!("generating robots.txt file");
+
+    let robots = settings.robots.clone().unwrap_or_default();
+    let mut robots_txt = String::from("User-agent: *\n");
+    let allow = match (robots.allow, &robots.disallow) {
+        (Some(allow), _) => allow,
+        (None, None) => vec!["/".to_string()],
+        (None, Some(_)) => vec![],
+    };
+    for path in allow {
+        _ = writeln!(robots_txt, "Allow: {path}");
+    }
+    for path in robots.disallow.unwrap_or_default() {
+        _ = writeln!(robots_txt, "Disallow: {path}");
+    }
+    if let Some(sitemap_url) = &robots.sitemap_url {
+        _ = writeln!(robots_txt, "\nSitemap: {sitemap_url}");
+    }
+    File::create(output_dir.join("robots.txt"))?.write_all(robots_txt.as_bytes())?;
+
+    Ok(())
+}
+
+/// Generate a file that maps each landscape item to its logo in the output
+/// directory (items whose logo could not be prepared are not included).
+#[instrument(skip_all, err)]
+fn generate_logos_mapping_file(landscape_data: &LandscapeData, output_dir: &Path) -> Result<()> {
+    debug!("generating logos mapping file");
+
+    let mapping: BTreeMap<&str, &str> = landscape_data
+        .items
+        .iter()
+        .filter(|item| !item.logo.is_empty())
+        .map(|item| (item.name.as_str(), item.logo.as_str()))
+        .collect();
+    let path = output_dir.join(DOCS_PATH).join("logos.json");
+    File::create(path)?.write_all(&serde_json::to_vec_pretty(&mapping)?)?;
+
+    Ok(())
+}
+
+/// Generate a report listing the logos shared by more than one item (grouped
+/// by the logo digest), so that curators can confirm the sharing is
+/// intentional (i.e. an item may have got another item's logo by mistake).
+#[instrument(skip_all, err)]
+fn generate_shared_logos_report(landscape_data: &LandscapeData, output_dir: &Path) -> Result<()> {
+    debug!("generating shared logos report");
+
+    // Collect the items using each logo (logos file names are their digest).
+    // The default logo is shared on purpose, so it is not reported.
+    let default_logo_path = format!("{LOGOS_PATH}/{DEFAULT_LOGO_FILE_NAME}");
+    let mut logos_items: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for item in landscape_data
+        .items
+        .iter()
+        .filter(|item| !item.logo.is_empty() && item.logo != default_logo_path)
+    {
+        let digest =
+            Path::new(&item.logo).file_stem().and_then(std::ffi::OsStr::to_str).unwrap_or(&item.logo);
+        logos_items.entry(digest).or_default().push(item.name.as_str());
+    }
+    logos_items.retain(|_, items| items.len() > 1);
+    for items in logos_items.values_mut() {
+        items.sort_unstable();
+    }
+
+    if !logos_items.is_empty() {
+        info!(
+            logos = logos_items.len(),
+            "some logos are shared by more than one item (see {DOCS_PATH}/shared_logos.json)"
+        );
+    }
+    let path = output_dir.join(DOCS_PATH).join("shared_logos.json");
+    File::create(path)?.write_all(&serde_json::to_vec_pretty(&logos_items)?)?;
+
+    Ok(())
+}
+
 /// Generate the projects.md and projects.csv files from the landscape data.
+/// Projects are sorted by name unless the order in the landscape data must be
+/// kept. When a custom template is provided, it will be used to render the
+/// projects.md file instead of the default one.
 #[instrument(skip_all, err)]
-fn generate_projects_files(landscape_data: &LandscapeData, output_dir: &Path) -> Result<()> {
+fn generate_projects_files(
+    landscape_data: &LandscapeData,
+    keep_order: bool,
+    projects_template: &Option<PathBuf>,
+    output_dir: &Path,
+) -> Result<()> {
     debug!("generating projects files");
 
-    let projects: Vec<Project> = landscape_data.into();
+    let mut projects: Vec<Project> = landscape_data.into();
+    if !keep_order {
+        projects.sort_by(|a, b| a.name.cmp(&b.name));
+    }
 
     // projects.md
-    let projects_md = ProjectsMd { projects: &projects }.render()?;
+    let projects_md = if let Some(template) = projects_template {
+        let template = fs::read_to_string(template).context("error reading projects template")?;
+        render_custom_projects_md(&template, &projects).context("error rendering projects template")?
+    } else {
+        ProjectsMd { projects: &projects }.render()?
+    };
     let docs_path = output_dir.join(DOCS_PATH);
     let mut file = File::create(docs_path.join("projects.md"))?;
     file.write_all(projects_md.as_bytes())?;
@@ -214,23 +1044,122 @@ fn generate_projects_files(landscape_data: &LandscapeData, output_dir: &Path) ->
     Ok(())
 }
 
+/// Template for the categories markdown files.
+#[derive(Debug, Clone, Template)]
+#[template(path = "category.md")]
+struct CategoryMd<'a> {
+    category: &'a str,
+    items: Vec<CategoryMdItem<'a>>,
+}
+
+/// Item entry in the category markdown file.
+#[derive(Debug, Clone)]
+struct CategoryMdItem<'a> {
+    name: &'a str,
+    homepage_url: &'a str,
+    description: String,
+    repository: &'a str,
+    stars: String,
+}
+
+/// Generate a markdown file for each of the categories, containing a table
+/// with the category items. Items are sorted by name unless the order in the
+/// landscape data must be kept.
+#[instrument(skip_all, err)]
+fn generate_categories_md_files(
+    landscape_data: &LandscapeData,
+    keep_order: bool,
+    output_dir: &Path,
+) -> Result<()> {
+    debug!("generating categories markdown files");
+
+    let categories_path = output_dir.join(DOCS_PATH).join("categories");
+    if !categories_path.exists() {
+        fs::create_dir(&categories_path)?;
+    }
+
+    let slugs = categories_slugs(&landscape_data.categories);
+    for category in &landscape_data.categories {
+        let mut items: Vec<CategoryMdItem> = landscape_data
+            .items
+            .iter()
+            .filter(|i| i.category == category.name)
+            .map(|i| {
+                let repository = i.primary_repository();
+                CategoryMdItem {
+                    name: &i.name,
+                    homepage_url: &i.homepage_url,
+                    // Only the first line is used, and pipes are escaped so
+                    // that the table is not broken
+                    description: i
+                        .description()
+                        .and_then(|d| d.lines().map(str::trim).find(|l| !l.is_empty()))
+                        .unwrap_or_default()
+                        .replace('|', "\\|"),
+                    repository: repository.map_or("", |r| r.url.as_str()),
+                    stars: repository
+                        .and_then(|r| r.github_data.as_ref())
+                        .map_or(String::new(), |gh_data| gh_data.stars.to_string()),
+                }
+            })
+            .collect();
+        if !keep_order {
+            items.sort_by_key(|item| item.name.to_lowercase());
+        }
+
+        let category_md = CategoryMd {
+            category: &category.name,
+            items,
+        }
+        .render()?;
+        let file_name = format!("{}.md", slugs[category.name.as_str()]);
+        File::create(categories_path.join(file_name))?.write_all(category_md.as_bytes())?;
+    }
+
+    Ok(())
+}
+
 /// Generate the items.csv file from the landscape data.
 #[instrument(skip_all, err)]
-fn generate_items_csv_file(landscape_data: &LandscapeData, output_dir: &Path) -> Result<()> {
+fn generate_items_csv_file(
+    landscape_data: &LandscapeData,
+    keep_order: bool,
+    output_dir: &Path,
+) -> Result<()> {
     debug!("generating items csv file");
 
     let docs_path = output_dir.join(DOCS_PATH);
     let w = csv::Writer::from_path(docs_path.join("items.csv"))?;
-    generate_items_csv(w, landscape_data)?;
+    generate_items_csv(w, landscape_data, keep_order)?;
 
     Ok(())
 }
 
-/// Get settings images and copy them to the output directory.
+/// Generate the landscape graph file in the format provided.
 #[instrument(skip_all, err)]
-async fn get_settings_images(settings: &LandscapeSettings, output_dir: &Path) -> Result<Images> {
+fn generate_graph_file(landscape_data: &LandscapeData, format: GraphFormat, output_dir: &Path) -> Result<()> {
+    debug!("generating graph file");
+
+    let file_name = match format {
+        GraphFormat::Dot => "landscape.dot",
+        GraphFormat::Graphml => "landscape.graphml",
+    };
+    let w = File::create(output_dir.join(DOCS_PATH).join(file_name))?;
+    generate_graph(w, landscape_data, format)?;
+
+    Ok(())
+}
+
+/// Get settings images and copy them to the output directory. Images that
+/// cannot be processed are omitted, unless fail fast has been requested.
+#[instrument(skip_all, err)]
+async fn get_settings_images(
+    settings: &LandscapeSettings,
+    fail_fast: bool,
+    output_dir: &Path,
+) -> Result<Images> {
     // Helper function to process the image provided
-    async fn process_image(url: &Option<String>, output_dir: &Path) -> Result<Option<String>> {
+    async fn process_image(image: &str, url: Option<&str>, output_dir: &Path) -> Result<Option<String>> {
         let Some(url) = url else {
             return Ok(None);
         };
@@ -245,6 +1174,16 @@ async fn get_settings_images(settings: &LandscapeSettings, output_dir: &Path) ->
         }
         let img = resp.bytes().await?.to_vec();
 
+        // Check image dimensions
+        match get_image_size(&img) {
+            Ok((width, height)) => {
+                for issue in find_settings_image_issues(image, width, height) {
+                    warnings::add(WarningKind::SettingsImage, None, issue);
+                }
+            }
+            Err(err) => warn!(?err, ?image, "error getting settings image size"),
+        }
+
         // Write image to output dir
         let url = Url::parse(url).context("invalid image url")?;
         let Some(file_name) = url.path_segments().and_then(Iterator::last) else {
@@ -259,41 +1198,163 @@ async fn get_settings_images(settings: &LandscapeSettings, output_dir: &Path) ->
 
     debug!("getting settings images");
 
-    let (favicon, footer_logo, header_logo) = tokio::try_join!(
-        process_image(&settings.images.favicon, output_dir),
-        process_image(&settings.images.footer_logo, output_dir),
-        process_image(&settings.images.header_logo, output_dir),
-    )?;
+    let (favicon, footer_logo, header_logo) = if fail_fast {
+        tokio::try_join!(
+            process_image("favicon", settings.images.favicon.as_deref(), output_dir),
+            process_image("footer_logo", settings.images.footer_logo.as_deref(), output_dir),
+            process_image("header_logo", settings.images.header_logo.as_deref(), output_dir),
+        )?
+    } else {
+        let omit_on_error = |image: &str, result: Result<Option<String>>| match result {
+            Ok(path) => path,
+            Err(err) => {
+                error!(?err, ?image, "error getting settings image");
+                warnings::add(
+                    WarningKind::SettingsImage,
+                    None,
+                    format!("error getting {image} image (omitted): {err}"),
+                );
+                None
+            }
+        };
+        let (favicon, footer_logo, header_logo) = tokio::join!(
+            process_image("favicon", settings.images.favicon.as_deref(), output_dir),
+            process_image("footer_logo", settings.images.footer_logo.as_deref(), output_dir),
+            process_image("header_logo", settings.images.header_logo.as_deref(), output_dir),
+        );
+        (
+            omit_on_error("favicon", favicon),
+            omit_on_error("footer_logo", footer_logo),
+            omit_on_error("header_logo", header_logo),
+        )
+    };
+
+    // Generate header and footer logos variants for hi-DPI displays
+    let srcset = |image: &str, path: &Option<String>, display_height: u32| {
+        let path = path.as_ref()?;
+        match generate_settings_logo_variants(image, path, display_height, output_dir) {
+            Ok(srcset) => srcset,
+            Err(err) => {
+                warnings::add(
+                    WarningKind::SettingsImage,
+                    None,
+                    format!("error generating {image} image variants: {err}"),
+                );
+                None
+            }
+        }
+    };
+    let footer_logo_srcset = srcset("footer_logo", &footer_logo, SETTINGS_FOOTER_LOGO_HEIGHT);
+    let header_logo_srcset = srcset("header_logo", &header_logo, SETTINGS_HEADER_LOGO_HEIGHT);
+
     let images = Images {
         favicon,
         footer_logo,
+        footer_logo_srcset,
         header_logo,
+        header_logo_srcset,
         open_graph: settings.images.open_graph.clone(),
     };
 
     Ok(images)
 }
 
+/// Generate some variants of the settings logo provided, resized for the
+/// display height given at different pixel densities, and return a srcset
+/// value referencing them. Variants are only generated for PNG images (SVG
+/// images are resolution independent), and never upscaled: the original
+/// image is used for the largest density it is able to cover. The variants
+/// are named after the kind of image (i.e. header_logo@2x.png), so that the
+/// ones generated for different images never overwrite each other.
+fn generate_settings_logo_variants(
+    image: &str,
+    img_path: &str,
+    display_height: u32,
+    output_dir: &Path,
+) -> Result<Option<String>> {
+    let img = fs::read(output_dir.join(img_path))?;
+    if detect_raster_format(&img) != Some("PNG") {
+        return Ok(None);
+    }
+    let (_, height) = get_image_size(&img)?;
+
+    let mut srcset = vec![];
+    for density in SETTINGS_LOGO_DENSITIES {
+        let variant_height = display_height * density;
+        #[allow(clippy::cast_precision_loss)]
+        if variant_height as f32 >= height {
+            srcset.push(format!("{img_path} {density}x"));
+            break;
+        }
+        let variant_path = Path::new(IMAGES_PATH).join(format!("{image}@{density}x.png"));
+        fs::write(output_dir.join(&variant_path), resize_png(&img, variant_height)?)?;
+        srcset.push(format!("{} {density}x", variant_path.to_string_lossy()));
+    }
+
+    Ok(Some(srcset.join(", ")))
+}
+
+/// Find some issues in the dimensions of the settings image provided that may
+/// make it look broken in the web application (i.e. a favicon that isn't
+/// square or a logo that is too tall or too wide).
+fn find_settings_image_issues(image: &str, width: f32, height: f32) -> Vec<String> {
+    let mut issues = vec![];
+    if width <= 0.0 || height <= 0.0 {
+        issues.push(format!("{image} image has invalid dimensions ({width}x{height})"));
+        return issues;
+    }
+
+    let aspect_ratio = width / height;
+    match image {
+        "favicon" if (aspect_ratio - 1.0).abs() > f32::EPSILON => {
+            issues.push(format!("{image} image is not square ({width}x{height})"));
+        }
+        "footer_logo" | "header_logo"
+            if !(SETTINGS_LOGO_MIN_ASPECT_RATIO..=SETTINGS_LOGO_MAX_ASPECT_RATIO).contains(&aspect_ratio) =>
+        {
+            issues.push(format!(
+                "{image} image aspect ratio is out of bounds ({width}x{height}, expecting a width between {SETTINGS_LOGO_MIN_ASPECT_RATIO} and {SETTINGS_LOGO_MAX_ASPECT_RATIO} times its height)"
+            ));
+        }
+        _ => {}
+    }
+
+    issues
+}
+
 /// Prepare guide and copy it to the output directory.
 #[instrument(skip_all, err)]
-async fn prepare_guide(guide_source: &GuideSource, output_dir: &Path) -> Result<Option<()>> {
+async fn prepare_guide(
+    guide_source: &GuideSource,
+    settings: &LandscapeSettings,
+    output_dir: &Path,
+) -> Result<Option<LandscapeGuide>> {
     debug!("preparing guide");
 
-    let Some(guide) = LandscapeGuide::new(guide_source).await? else {
+    let html_sanitization = settings.html_sanitization.clone().unwrap_or_default();
+    let sanitizer = html_sanitization.sanitizer();
+    let Some(guide) = LandscapeGuide::new(guide_source, &sanitizer).await? else {
         return Ok(None);
     };
     let path = output_dir.join(DATASETS_PATH).join("guide.json");
     File::create(path)?.write_all(&serde_json::to_vec(&guide)?)?;
 
-    Ok(Some(()))
+    Ok(Some(guide))
 }
 
 /// Prepare items logos and copy them to the output directory, updating the
-/// logo reference on each landscape item.
+/// logo reference on each landscape item. Items' logo mirrors are tried when
+/// the logo cannot be obtained from the logos source. When requested, the
+/// item's homepage favicon will be used for items whose logo could not be
+/// prepared. When a shard length is provided, logos are written to
+/// subdirectories named after the first characters of their digest.
 #[instrument(skip_all, err)]
 async fn prepare_items_logos(
-    cache: &Cache,
+    cache: &DynCache,
     logos_source: &LogosSource,
+    favicon_fallback: bool,
+    default_logo: &Option<PathBuf>,
+    shard_length: Option<usize>,
     landscape_data: &mut LandscapeData,
     output_dir: &Path,
 ) -> Result<()> {
@@ -306,32 +1367,96 @@ async fn prepare_items_logos(
     }
     let http_client = reqwest::Client::new();
     let logos_source = Arc::new(logos_source.clone());
-    let logos: HashMap<Uuid, Option<String>> = stream::iter(landscape_data.items.iter())
+    let logos: HashMap<Uuid, Option<(String, Option<String>)>> = stream::iter(landscape_data.items.iter())
         .map(|item| async {
             // Prepare logo
             let cache = cache.clone();
             let http_client = http_client.clone();
             let logos_source = logos_source.clone();
+            let item_id = item.id;
             let file_name = item.logo.clone();
+            let logo_mirrors = item.logo_mirrors.clone().unwrap_or_default();
+            let homepage_url = item.homepage_url.clone();
+            let item_name = item.name.clone();
+            let _permit = concurrency::acquire().await;
             let logo = match tokio::spawn(async move {
-                prepare_logo(&cache, http_client, &logos_source, &file_name).await
+                let result = if file_name.is_empty() {
+                    Err(format_err!("logo not provided"))
+                } else {
+                    prepare_logo(
+                        &cache,
+                        http_client.clone(),
+                        &logos_source,
+                        item_id,
+                        &file_name,
+                        &logo_mirrors,
+                    )
+                    .await
+                };
+
+                // Fall back to the item's homepage favicon if requested
+                match result {
+                    Err(err) if favicon_fallback && !homepage_url.is_empty() => {
+                        let logo = prepare_favicon_logo(&cache, http_client, &homepage_url)
+                            .await
+                            .map_err(|favicon_err| format_err!("{err} (favicon fallback: {favicon_err})"))?;
+                        warnings::add(
+                            WarningKind::Logo,
+                            Some(&item_name),
+                            format!("error preparing logo: {err} (homepage favicon used instead)"),
+                        );
+                        Ok(logo)
+                    }
+                    result => result,
+                }
             })
             .await
             {
-                Ok(Ok(logo)) => logo,
+                Ok(Ok(logo)) => {
+                    for issue in &logo.issues {
+                        warn!(?item.name, ?item.logo, issue, "logo issue found");
+                        warnings::add(WarningKind::Logo, Some(&item.name), issue.as_str());
+                    }
+                    logo
+                }
                 Ok(Err(err)) => {
-                    error!(?err, ?item.logo, "error preparing logo");
+                    error!(?err, ?item.name, ?item.logo, "error preparing logo");
+                    warnings::add(
+                        WarningKind::Logo,
+                        Some(&item.name),
+                        format!("error preparing logo: {err}"),
+                    );
                     return (item.id, None);
                 }
                 Err(err) => {
-                    error!(?err, ?item.logo, "error executing prepare_logo task");
+                    error!(?err, ?item.name, ?item.logo, "error executing prepare_logo task");
+                    warnings::add(
+                        WarningKind::Logo,
+                        Some(&item.name),
+                        format!("error preparing logo: {err}"),
+                    );
                     return (item.id, None);
                 }
             };
 
             // Copy logo to output dir using the digest(+.svg) as filename
-            let file_name = format!("{}.svg", logo.digest);
-            let Ok(mut file) = fs::File::create(output_dir.join(LOGOS_PATH).join(&file_name)) else {
+            // (inside the corresponding shard directory if requested)
+            let mut file_name = format!("{}.svg", logo.digest);
+            if let Some(shard_length) = shard_length {
+                file_name = format!("{}/{file_name}", &logo.digest[..shard_length]);
+            }
+            let file_path = output_dir.join(LOGOS_PATH).join(&file_name);
+            if let Some(parent) = file_path.parent() {
+                if let Err(err) = fs::create_dir_all(parent) {
+                    error!(
+                        ?err,
+                        ?file_name,
+                        "error creating logo shard directory in output dir"
+                    );
+                    return (item.id, None);
+                }
+            }
+            let Ok(mut file) = fs::File::create(file_path) else {
                 error!(?file_name, "error creating logo file in output dir");
                 return (item.id, None);
             };
@@ -339,43 +1464,677 @@ async fn prepare_items_logos(
                 error!(?err, ?file_name, "error writing logo to file in output dir");
             };
 
-            (item.id, Some(format!("{LOGOS_PATH}/{file_name}")))
+            (
+                item.id,
+                Some((format!("{LOGOS_PATH}/{file_name}"), logo.dominant_color)),
+            )
         })
         .buffer_unordered(concurrency)
         .collect()
         .await;
 
-    // Update logo field in landscape items to logo digest path
+    // Copy the default logo to the output directory if it will be needed
+    let mut default_logo_path = String::new();
+    if let Some(default_logo) = default_logo {
+        if logos.values().any(Option::is_none) {
+            fs::copy(
+                default_logo,
+                output_dir.join(LOGOS_PATH).join(DEFAULT_LOGO_FILE_NAME),
+            )
+            .context("error copying default logo")?;
+            default_logo_path = format!("{LOGOS_PATH}/{DEFAULT_LOGO_FILE_NAME}");
+        }
+    }
+
+    // Update logo field in landscape items to logo digest path (or to the
+    // default logo when the item's logo could not be prepared)
     for item in &mut landscape_data.items {
-        item.logo = if let Some(Some(logo)) = logos.get(&item.id) {
-            logo.clone()
+        if let Some(Some((logo, dominant_color))) = logos.get(&item.id) {
+            item.logo = logo.clone();
+            item.logo_dominant_color = dominant_color.clone();
+        } else {
+            item.logo.clone_from(&default_logo_path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Verify that the output directory contains all the files required, like the
+/// index document or the datasets, as well as all the logos and images they
+/// reference.
+#[instrument(skip_all, err)]
+fn verify_output(
+    datasets: &Datasets,
+    datasets_only: bool,
+    assets_base_url: Option<&str>,
+    output_dir: &Path,
+) -> Result<()> {
+    debug!("verifying output directory");
+
+    // Get the local path of the asset provided. Assets urls may have been
+    // rewritten to point to the assets base url, in which case the base url is
+    // stripped. Assets hosted externally are not copied to the output
+    // directory, so they are ignored.
+    let assets_base_url = assets_base_url.map(|url| format!("{}/", url.trim_end_matches('/')));
+    let local_path = |path: &str| -> Option<String> {
+        if let Some(path) = assets_base_url.as_deref().and_then(|url| path.strip_prefix(url)) {
+            return Some(path.to_string());
+        }
+        if Url::parse(path).is_ok() {
+            return None;
+        }
+        Some(path.to_string())
+    };
+
+    // Collect paths that must be present in the output directory
+    let mut required_paths = vec![
+        format!("{DATASETS_PATH}/base.json"),
+        format!("{DATASETS_PATH}/full.json"),
+    ];
+    if !datasets_only {
+        required_paths.push("index.html".to_string());
+    }
+    let images = &datasets.base.images;
+    for image in [
+        &images.favicon,
+        &images.footer_logo,
+        &images.header_logo,
+        &images.open_graph,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        required_paths.extend(local_path(image));
+    }
+    for item in &datasets.base.items {
+        if !item.logo.is_empty() {
+            required_paths.extend(local_path(&item.logo));
+        }
+    }
+    required_paths.sort();
+    required_paths.dedup();
+
+    // Check all required paths are present and not empty
+    let mut errors = String::new();
+    for path in required_paths {
+        match fs::metadata(output_dir.join(&path)) {
+            Ok(metadata) if metadata.len() > 0 => {}
+            Ok(_) => _ = writeln!(errors, "- {path}: file is empty"),
+            Err(_) => _ = writeln!(errors, "- {path}: file not found"),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(format_err!("output directory verification failed:\n{errors}"));
+    }
+
+    Ok(())
+}
+
+/// Template for the embeddable widget.
+#[derive(Debug, Clone, Template)]
+#[template(path = "widget.html")]
+struct Widget<'a> {
+    category: &'a str,
+    colors: Colors,
+    foundation: &'a str,
+    subcategories: Vec<WidgetSubcategory>,
+}
+
+/// Widget dataset.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+struct WidgetData<'a> {
+    category: &'a str,
+    subcategories: &'a [WidgetSubcategory],
+}
+
+/// Widget subcategory.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+struct WidgetSubcategory {
+    name: String,
+    items: Vec<WidgetItem>,
+}
+
+/// Widget item.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+struct WidgetItem {
+    id: Uuid,
+    logo: String,
+    name: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+}
+
+/// Generate an embeddable widget (widget.html) displaying the items in the
+/// category provided, as well as its dataset (data/widget.json). The widget is
+/// self-contained (logos are inlined), so that it can be embedded in other
+/// sites using an iframe. When an absolute base href is provided, items link
+/// to their page in the landscape.
+#[instrument(skip_all, err)]
+fn generate_widget(
+    settings: &LandscapeSettings,
+    datasets: &Datasets,
+    category: &str,
+    base_href: Option<&str>,
+    output_dir: &Path,
+) -> Result<()> {
+    debug!(?category, "generating widget");
+
+    // Prepare the widget subcategories (in the same order used in the
+    // landscape) with the category items
+    let Some(dataset_category) = datasets.base.categories.iter().find(|c| c.name == category) else {
+        return Err(format_err!("widget category not found: {category}"));
+    };
+    let base_url = base_href.and_then(|base_href| Url::parse(base_href).ok());
+    let subcategories: Vec<WidgetSubcategory> = dataset_category
+        .subcategories
+        .iter()
+        .map(|subcategory| WidgetSubcategory {
+            name: subcategory.clone(),
+            items: datasets
+                .base
+                .items
+                .iter()
+                .filter(|item| item.category == category && &item.subcategory == subcategory)
+                .map(|item| WidgetItem {
+                    id: item.id,
+                    logo: item.logo.clone(),
+                    name: item.name.clone(),
+                    url: base_url.as_ref().map(|base_url| {
+                        let mut item_url = base_url.clone();
+                        item_url.query_pairs_mut().append_pair("item", &item.id.to_string());
+                        item_url.to_string()
+                    }),
+                })
+                .collect(),
+        })
+        .filter(|subcategory| !subcategory.items.is_empty())
+        .collect();
+
+    // Write widget dataset
+    let widget_data = WidgetData {
+        category,
+        subcategories: &subcategories,
+    };
+    File::create(output_dir.join(DATASETS_PATH).join("widget.json"))?
+        .write_all(&serde_json::to_vec(&widget_data)?)?;
+
+    // Inline the items logos (logos hosted externally are kept as they are,
+    // and items without logo are displayed using their name)
+    let mut inlined_subcategories = subcategories.clone();
+    for item in inlined_subcategories.iter_mut().flat_map(|s| s.items.iter_mut()) {
+        if !item.logo.is_empty() && Url::parse(&item.logo).is_err() {
+            let data = fs::read(output_dir.join(&item.logo))?;
+            item.logo = data_uri(&item.logo, &data);
+        }
+    }
+
+    // Render widget and write it to the output directory
+    let widget = Widget {
+        category,
+        colors: settings.colors.clone().unwrap_or_default(),
+        foundation: &settings.foundation,
+        subcategories: inlined_subcategories,
+    }
+    .render()?;
+    File::create(output_dir.join("widget.html"))?.write_all(widget.as_bytes())?;
+
+    Ok(())
+}
+
+/// Template for the error page.
+#[derive(Debug, Clone, Template)]
+#[template(path = "404.html")]
+struct ErrorPage<'a> {
+    colors: Colors,
+    foundation: &'a str,
+    header_logo: Option<String>,
+    home_url: String,
+}
+
+/// Generate the error page (404.html) and write it to the output directory.
+/// When a custom error page is provided, it will be copied instead.
+#[instrument(skip_all, err)]
+fn generate_error_page(
+    settings: &LandscapeSettings,
+    error_page: &Option<PathBuf>,
+    base_href: Option<&str>,
+    output_dir: &Path,
+) -> Result<()> {
+    debug!("generating error page");
+
+    let path = output_dir.join("404.html");
+    if let Some(error_page) = error_page {
+        fs::copy(error_page, path).context("error copying error page")?;
+        return Ok(());
+    }
+
+    // The error page may be served from any path, so the landscape home and
+    // the local images are referenced from the base href (or the root)
+    let home_url = base_href.map_or("/".to_string(), |base_href| {
+        format!("{}/", base_href.trim_end_matches('/'))
+    });
+    let header_logo = settings.images.header_logo.as_ref().map(|header_logo| {
+        if Url::parse(header_logo).is_ok() {
+            header_logo.clone()
         } else {
-            String::new()
+            format!("{home_url}{header_logo}")
         }
+    });
+    let error_page = ErrorPage {
+        colors: settings.colors.clone().unwrap_or_default(),
+        foundation: &settings.foundation,
+        header_logo,
+        home_url,
     }
+    .render()?;
+    File::create(path)?.write_all(error_page.as_bytes())?;
 
     Ok(())
 }
 
+/// Template for the feeds.opml file.
+#[derive(Debug, Clone, Template)]
+#[template(path = "feeds.opml", escape = "html")]
+struct FeedsOpml<'a> {
+    foundation: &'a str,
+    items: Vec<FeedsOpmlItem<'a>>,
+}
+
+/// Item entry in the feeds.opml file.
+#[derive(Debug, Clone)]
+struct FeedsOpmlItem<'a> {
+    name: &'a str,
+    feed_url: &'a str,
+    html_url: &'a str,
+}
+
+/// Generate the feeds.opml file, aggregating the feeds of the items that
+/// provide one, and write it to the output directory.
+#[instrument(skip_all, err)]
+fn generate_feeds_opml_file(
+    settings: &LandscapeSettings,
+    landscape_data: &LandscapeData,
+    output_dir: &Path,
+) -> Result<()> {
+    debug!("generating feeds.opml file");
+
+    let items = landscape_data
+        .items
+        .iter()
+        .filter_map(|i| {
+            Some(FeedsOpmlItem {
+                name: &i.name,
+                feed_url: i.feed_url.as_deref()?,
+                html_url: i.blog_url.as_deref().unwrap_or(&i.homepage_url),
+            })
+        })
+        .collect();
+
+    let feeds_opml = FeedsOpml {
+        foundation: &settings.foundation,
+        items,
+    }
+    .render()?;
+    File::create(output_dir.join("feeds.opml"))?.write_all(feeds_opml.as_bytes())?;
+
+    Ok(())
+}
+
+/// Template for the llms.txt file.
+#[derive(Debug, Clone, Template)]
+#[template(path = "llms.txt")]
+struct LlmsTxt<'a> {
+    foundation: &'a str,
+    sections: Vec<LlmsTxtSection<'a>>,
+}
+
+/// Section of the llms.txt file (one per subcategory).
+#[derive(Debug, Clone)]
+struct LlmsTxtSection<'a> {
+    title: String,
+    items: Vec<LlmsTxtItem<'a>>,
+}
+
+/// Item entry in the llms.txt file.
+#[derive(Debug, Clone)]
+struct LlmsTxtItem<'a> {
+    name: &'a str,
+    url: &'a str,
+    description: Option<&'a str>,
+}
+
+/// Generate the llms.txt file, summarizing the landscape categories and items
+/// following the llms.txt convention, and write it to the output directory.
+#[instrument(skip_all, err)]
+fn generate_llms_txt_file(
+    settings: &LandscapeSettings,
+    landscape_data: &LandscapeData,
+    output_dir: &Path,
+) -> Result<()> {
+    debug!("generating llms.txt file");
+
+    let mut sections = vec![];
+    for category in &landscape_data.categories {
+        for subcategory in &category.subcategories {
+            let items: Vec<LlmsTxtItem> = landscape_data
+                .items
+                .iter()
+                .filter(|i| i.category == category.name && &i.subcategory == subcategory)
+                .map(|i| LlmsTxtItem {
+                    name: &i.name,
+                    url: &i.homepage_url,
+                    description: i
+                        .description
+                        .as_deref()
+                        .and_then(|d| d.lines().map(str::trim).find(|l| !l.is_empty())),
+                })
+                .collect();
+            if !items.is_empty() {
+                sections.push(LlmsTxtSection {
+                    title: format!("{} / {subcategory}", category.name),
+                    items,
+                });
+            }
+        }
+    }
+
+    let llms_txt = LlmsTxt {
+        foundation: &settings.foundation,
+        sections,
+    }
+    .render()?;
+    File::create(output_dir.join("llms.txt"))?.write_all(llms_txt.as_bytes())?;
+
+    Ok(())
+}
+
+/// Generate a JSON-LD structured data block describing the landscape as a
+/// collection page listing its items (as they are included in the datasets,
+/// so it reflects any filtering applied during the build). When a base href is
+/// provided, the urls of the landscape and its items are included as well.
+fn generate_json_ld(datasets: &Datasets, base_href: Option<&str>) -> serde_json::Value {
+    let base_url = base_href.and_then(|base_href| Url::parse(base_href).ok());
+    let items: Vec<serde_json::Value> = datasets
+        .base
+        .items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let mut list_item = serde_json::json!({
+                "@type": "ListItem",
+                "position": i + 1,
+                "name": item.name,
+            });
+            if let Some(base_url) = &base_url {
+                let mut item_url = base_url.clone();
+                item_url.query_pairs_mut().append_pair("item", &item.id.to_string());
+                list_item["url"] = item_url.as_str().into();
+            }
+            list_item
+        })
+        .collect();
+
+    let mut json_ld = serde_json::json!({
+        "@context": "https://schema.org",
+        "@type": "CollectionPage",
+        "name": format!("{} landscape", datasets.base.foundation),
+        "mainEntity": {
+            "@type": "ItemList",
+            "numberOfItems": items.len(),
+            "itemListElement": items,
+        },
+    });
+    if let Some(base_url) = &base_url {
+        json_ld["url"] = base_url.as_str().into();
+    }
+
+    json_ld
+}
+
 /// Template for the index document.
 #[derive(Debug, Clone, Template)]
 #[template(path = "index.html", escape = "none")]
 struct Index<'a> {
+    custom_code: Option<&'a CustomCode>,
     datasets: &'a Datasets,
+    embed_full_dataset: bool,
+    guide: Option<&'a LandscapeGuide>,
+    json_ld: Option<&'a serde_json::Value>,
 }
 
-/// Render index file and write it to the output directory.
+/// Render index file and write it to the output directory. When a guide is
+/// provided, it will be embedded in the index document.
 #[instrument(skip_all, err)]
-fn render_index(datasets: &Datasets, output_dir: &Path) -> Result<()> {
+fn render_index(
+    datasets: &Datasets,
+    guide: Option<&LandscapeGuide>,
+    custom_code: Option<&CustomCode>,
+    json_ld: Option<&serde_json::Value>,
+    output_dir: &Path,
+) -> Result<()> {
     debug!("rendering index.html file");
 
-    let index = Index { datasets }.render()?;
+    let index = Index {
+        custom_code,
+        datasets,
+        embed_full_dataset: false,
+        guide,
+        json_ld,
+    }
+    .render()?;
     let mut file = File::create(output_dir.join("index.html"))?;
     file.write_all(index.as_bytes())?;
 
     Ok(())
 }
 
+/// Render a self-contained index file and write it to the output directory.
+/// The web application assets, the datasets, the logos and the guide (if
+/// provided) are inlined in the index document.
+#[instrument(skip_all, err)]
+fn render_single_file_index(
+    datasets: &Datasets,
+    guide: Option<&LandscapeGuide>,
+    custom_code: Option<&CustomCode>,
+    json_ld: Option<&serde_json::Value>,
+    output_dir: &Path,
+) -> Result<()> {
+    debug!("rendering single file index.html file");
+
+    let mut datasets = datasets.clone();
+    inline_datasets_files(&mut datasets, output_dir)?;
+    let index = Index {
+        custom_code,
+        datasets: &datasets,
+        embed_full_dataset: true,
+        guide,
+        json_ld,
+    }
+    .render()?;
+    let index = inline_web_assets(&index);
+    let mut file = File::create(output_dir.join("index.html"))?;
+    file.write_all(index.as_bytes())?;
+
+    Ok(())
+}
+
+/// Remove the cache entries that are not referenced by the landscape data
+/// anymore (i.e. logos of items that have been removed or updated).
+#[instrument(skip_all, err)]
+async fn prune_cache(cache: &DynCache, landscape_data: &LandscapeData) -> Result<()> {
+    debug!("pruning cache");
+
+    // Logos (and their dominant color) are cached using their digest, which
+    // is also used as the file name of the logos in the output directory.
+    // Logos obtained from a mirror are cached using the item's id.
+    let mut referenced_logos: HashSet<String> = landscape_data
+        .items
+        .iter()
+        .filter_map(|item| Path::new(&item.logo).file_stem())
+        .flat_map(|digest| {
+            let digest = digest.to_string_lossy();
+            [format!("logo_{digest}.svg"), format!("logo_{digest}.color")]
+        })
+        .collect();
+    referenced_logos.extend(
+        landscape_data
+            .items
+            .iter()
+            .filter(|item| item.logo_mirrors.is_some())
+            .map(|item| format!("logo_mirror_{}.json", item.id)),
+    );
+
+    // Remove logos not referenced from the cache
+    let mut removed_entries = 0;
+    let mut reclaimed_bytes = 0;
+    for (file_name, size) in cache.list().await? {
+        if file_name.starts_with("logo_") && !referenced_logos.contains(&file_name) {
+            cache.remove(&file_name).await?;
+            removed_entries += 1;
+            reclaimed_bytes += size;
+        }
+    }
+
+    // Remove stars history of repositories not in the landscape
+    reclaimed_bytes += prune_stars_history(cache, landscape_data).await?;
+
+    info!("cache pruned: {removed_entries} entries removed ({reclaimed_bytes} bytes reclaimed)");
+
+    Ok(())
+}
+
+/// Check that none of the files in the logos and images output directories
+/// collide when their paths are compared case-insensitively, as they would
+/// overwrite each other on case-insensitive filesystems (i.e. on macOS or in
+/// some CDNs).
+#[instrument(skip_all, err)]
+fn check_output_paths_collisions(output_dir: &Path) -> Result<()> {
+    debug!("checking output paths collisions");
+
+    let mut paths: HashMap<String, String> = HashMap::new();
+    let mut collisions = vec![];
+    for dir in [IMAGES_PATH, LOGOS_PATH] {
+        let dir = output_dir.join(dir);
+        if !dir.exists() {
+            continue;
+        }
+        for entry in WalkDir::new(&dir).min_depth(1) {
+            let entry = entry?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let path = entry.path().strip_prefix(output_dir)?.to_string_lossy().into_owned();
+            if let Some(other_path) = paths.insert(path.to_lowercase(), path.clone()) {
+                collisions.push(format!("{other_path} <-> {path}"));
+            }
+        }
+    }
+    if !collisions.is_empty() {
+        collisions.sort();
+        return Err(format_err!(
+            "some output paths collide case-insensitively: {}",
+            collisions.join(", ")
+        ));
+    }
+
+    Ok(())
+}
+
+/// Remove the logos in the output directory that are not referenced by any of
+/// the landscape items. Logos are named after their digest, so when they
+/// change the previous versions are left behind. Logos in shard directories
+/// are pruned as well.
+#[instrument(skip_all, err)]
+fn prune_output_logos(landscape_data: &LandscapeData, output_dir: &Path) -> Result<()> {
+    debug!("pruning output logos");
+
+    let referenced_logos: HashSet<&str> = landscape_data
+        .items
+        .iter()
+        .filter_map(|item| item.logo.strip_prefix(&format!("{LOGOS_PATH}/")))
+        .collect();
+
+    let logos_path = output_dir.join(LOGOS_PATH);
+    let mut removed_logos = 0;
+    for entry in WalkDir::new(&logos_path).min_depth(1) {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let file_name = entry.path().strip_prefix(&logos_path)?.to_string_lossy();
+        if referenced_logos.contains(file_name.as_ref()) {
+            continue;
+        }
+        fs::remove_file(entry.path())?;
+        removed_logos += 1;
+    }
+    debug!(removed_logos, "output logos pruned");
+
+    Ok(())
+}
+
+/// Run the post build command provided using the shell. The output directory
+/// path is made available to the command in an environment variable.
+#[instrument(skip_all, err)]
+async fn run_post_build_command(command: &str, output_dir: &Path) -> Result<()> {
+    debug!(?command, "running post build command");
+
+    let status = tokio::process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .env(OUTPUT_DIR_ENV_VAR, fs::canonicalize(output_dir)?)
+        .status()
+        .await
+        .context("error running post build command")?;
+    if !status.success() {
+        return Err(format_err!("post build command failed ({status})"));
+    }
+
+    Ok(())
+}
+
+/// Run the transform command provided using the shell. The landscape data is
+/// written to the command's stdin in JSON format, and the transformed data is
+/// read from its stdout (it must use the same format).
+#[instrument(skip_all, err)]
+async fn run_transform_command(command: &str, landscape_data: &LandscapeData) -> Result<LandscapeData> {
+    debug!(?command, "running transform command");
+
+    let mut child = tokio::process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .context("error running transform command")?;
+
+    // Write the data to the command's stdin while reading its output
+    let input = serde_json::to_vec(landscape_data)?;
+    let mut stdin = child.stdin.take().ok_or_else(|| format_err!("error opening transform command stdin"))?;
+    let write_input = async move {
+        stdin.write_all(&input).await?;
+        drop(stdin);
+        Ok::<(), std::io::Error>(())
+    };
+    let (write_result, output) = tokio::join!(write_input, child.wait_with_output());
+    let output = output.context("error running transform command")?;
+    if !output.status.success() {
+        return Err(format_err!("transform command failed ({})", output.status));
+    }
+    // The command may exit without reading all the data provided
+    if let Err(err) = write_result {
+        if err.kind() != std::io::ErrorKind::BrokenPipe {
+            return Err(err).context("error writing data to transform command");
+        }
+    }
+
+    let landscape_data =
+        serde_json::from_slice(&output.stdout).context("invalid data returned by transform command")?;
+    Ok(landscape_data)
+}
+
 /// Setup output directory, creating it as well as any of the other required
 /// paths inside it when needed.
 #[instrument(fields(?output_dir), skip_all, err)]
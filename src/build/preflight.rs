@@ -0,0 +1,44 @@
+//! This module provides the functionality used to check that the external
+//! services configured are reachable and authorized before running a build
+//! (pre-flight checks).
+
+use super::{crunchbase::check_crunchbase, github::check_github};
+use crate::BuildArgs;
+use anyhow::{format_err, Result};
+use tracing::{error, info, instrument, warn};
+
+/// Check the external services configured (GitHub and Crunchbase) are
+/// reachable and that the credentials provided are authorized, reporting the
+/// status of each of them. An error is returned if any of the checks fails.
+#[instrument(skip_all, err)]
+pub(crate) async fn preflight(args: &BuildArgs) -> Result<()> {
+    info!("running pre-flight checks..");
+    let mut failed = vec![];
+
+    // GitHub
+    match check_github(&args.github_base_url, &args.github_tokens_file).await {
+        Ok(Some(tokens)) => info!(tokens, "github: reachable and authorized"),
+        Ok(None) => warn!("github: skipped (tokens not provided)"),
+        Err(err) => {
+            error!(?err, "github: check failed");
+            failed.push("github");
+        }
+    }
+
+    // Crunchbase
+    match check_crunchbase().await {
+        Ok(true) => info!("crunchbase: reachable and authorized"),
+        Ok(false) => warn!("crunchbase: skipped (api key not provided)"),
+        Err(err) => {
+            error!(?err, "crunchbase: check failed");
+            failed.push("crunchbase");
+        }
+    }
+
+    if !failed.is_empty() {
+        return Err(format_err!("pre-flight checks failed: {}", failed.join(", ")));
+    }
+    info!("pre-flight checks passed!");
+
+    Ok(())
+}
@@ -34,7 +34,7 @@ impl From<&LandscapeData> for Vec<Project> {
         };
 
         // Collect projects from landscape data
-        let mut projects: Vec<Project> = landscape_data
+        let projects: Vec<Project> = landscape_data
             .items
             .iter()
             .cloned()
@@ -72,9 +72,6 @@ impl From<&LandscapeData> for Vec<Project> {
             })
             .collect();
 
-        // Sort projects
-        projects.sort_by(|a, b| a.name.cmp(&b.name));
-
         projects
     }
 }
@@ -86,6 +83,16 @@ pub(crate) struct ProjectsMd<'a> {
     pub projects: &'a [Project],
 }
 
+/// Render the projects.md file using the custom template provided. Custom
+/// templates use the Jinja2 syntax and get the same context as the default
+/// one (`projects`).
+pub(crate) fn render_custom_projects_md(template: &str, projects: &[Project]) -> Result<String> {
+    let mut env = minijinja::Environment::new();
+    env.add_template("projects.md", template)?;
+    let projects_md = env.get_template("projects.md")?.render(minijinja::context! { projects })?;
+    Ok(projects_md)
+}
+
 /// Generate CSV file with some information about each project.
 pub(crate) fn generate_projects_csv(mut w: csv::Writer<File>, projects: &[Project]) -> Result<()> {
     // Write headers
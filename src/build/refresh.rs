@@ -0,0 +1,79 @@
+//! This module provides a mechanism to control which entries of the external
+//! data cache should be refreshed. By default, entries are refreshed once they
+//! expire. When a list of changed items is provided, only the external data of
+//! those items is refreshed, and the data of the rest of the items is always
+//! served from the cache (regardless of whether it has expired or not).
+
+use super::{github::get_owner_and_repo, LandscapeData};
+use anyhow::{format_err, Result};
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use std::{collections::HashSet, sync::RwLock};
+
+lazy_static! {
+    /// Keys of the cache entries to refresh (only set when a list of changed
+    /// items has been provided).
+    static ref KEYS_TO_REFRESH: RwLock<Option<HashSet<String>>> = RwLock::new(None);
+}
+
+/// Set the items (ids or names) whose external data should be refreshed. When
+/// no items are provided, cache entries are refreshed once they expire. The
+/// GitHub host is used to identify the repositories whose owner data
+/// (organization) is collected from GitHub.
+pub(crate) fn set_changed_items(
+    landscape_data: &LandscapeData,
+    changed_items: Option<&[String]>,
+    github_host: &str,
+) -> Result<()> {
+    let keys = changed_items
+        .map(|changed_items| {
+            let mut keys = HashSet::new();
+            for id_or_name in changed_items {
+                let Some(item) = landscape_data
+                    .items
+                    .iter()
+                    .find(|item| item.id.to_string() == *id_or_name || item.name == *id_or_name)
+                else {
+                    return Err(format_err!("changed item not found: {id_or_name}"));
+                };
+
+                // Collect the keys used to cache the item's external data
+                keys.insert(item.id.to_string());
+                for repo in item.repositories.iter().flatten() {
+                    keys.insert(repo.url.clone());
+                }
+                if let Some(owner) = item
+                    .primary_repository()
+                    .and_then(|repo| get_owner_and_repo(&repo.url, github_host).ok())
+                    .map(|(owner, _)| owner)
+                {
+                    keys.insert(owner);
+                }
+                keys.extend(
+                    [
+                        &item.bluesky_url,
+                        &item.crate_name,
+                        &item.crunchbase_url,
+                        &item.mastodon_url,
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .cloned(),
+                );
+            }
+            Ok(keys)
+        })
+        .transpose()?;
+
+    *KEYS_TO_REFRESH.write().expect("keys to refresh lock not to be poisoned") = keys;
+    Ok(())
+}
+
+/// Check if the cached data for the key provided can be used, or if it should
+/// be refreshed instead.
+pub(crate) fn is_cached_data_valid(key: &str, generated_at: DateTime<Utc>, ttl_days: i64) -> bool {
+    match &*KEYS_TO_REFRESH.read().expect("keys to refresh lock not to be poisoned") {
+        Some(keys) => !keys.contains(key),
+        None => generated_at + chrono::Duration::days(ttl_days) > Utc::now(),
+    }
+}
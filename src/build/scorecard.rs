@@ -0,0 +1,239 @@
+//! This module defines some types used to represent the OpenSSF Scorecard
+//! results collected for each of the landscape items repositories (when
+//! available), as well as the functionality used to collect them.
+
+use super::{
+    cache::DynCache,
+    concurrency,
+    github::{get_owner_and_repo, GITHUB_HOST},
+    metrics, refresh,
+    warnings::{self, WarningKind},
+    LandscapeData,
+};
+use anyhow::{format_err, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+#[cfg(test)]
+use mockall::automock;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+use tracing::{debug, instrument};
+
+/// File used to cache data collected from the OpenSSF Scorecard API.
+const SCORECARD_CACHE_FILE: &str = "scorecard.json";
+
+/// How long the Scorecard data in the cache is valid (in days).
+const SCORECARD_CACHE_TTL: i64 = 7;
+
+/// Maximum number of concurrent requests to the Scorecard API.
+const SCORECARD_MAX_CONCURRENCY: usize = 10;
+
+/// Collect the OpenSSF Scorecard results for each of the items GitHub
+/// repositories in the landscape, reusing cached data whenever possible. The
+/// repositories the Scorecard API has no results for are cached as well, so
+/// that they are not requested again until the cache entry expires.
+#[instrument(skip_all, err)]
+pub(crate) async fn collect_scorecard_data(
+    cache: &DynCache,
+    landscape_data: &LandscapeData,
+) -> Result<ScorecardData> {
+    debug!("collecting repositories scorecard results (this may take a while)");
+
+    // Read cached data (if available)
+    let mut cached_data: Option<CachedScorecardData> = None;
+    if let Ok(Some(json_data)) = cache.read(SCORECARD_CACHE_FILE).await {
+        if let Ok(scorecard_data) = serde_json::from_slice(&json_data) {
+            cached_data = Some(scorecard_data);
+        }
+    }
+
+    // Setup Scorecard API client
+    let sc: DynSC = Arc::new(SCApi::new()?);
+
+    // Collect urls of the repositories to process (the Scorecard API only
+    // supports repositories hosted on github.com)
+    let mut urls = vec![];
+    for item in &landscape_data.items {
+        if let Some(repositories) = &item.repositories {
+            for repo in repositories {
+                if get_owner_and_repo(&repo.url, GITHUB_HOST).is_ok() {
+                    urls.push(&repo.url);
+                }
+            }
+        }
+    }
+    urls.sort();
+    urls.dedup();
+
+    // Collect scorecard results, reusing cached data when available
+    let results: HashMap<String, Result<ScorecardLookup>> = stream::iter(urls)
+        .map(|url| async {
+            let url = url.clone();
+            let is_valid =
+                |generated_at| refresh::is_cached_data_valid(&url, generated_at, SCORECARD_CACHE_TTL);
+            if let Some(cached_scorecard) = cached_data.as_ref().and_then(|cache| {
+                if let Some(scorecard) = cache.scorecards.get(&url).filter(|s| is_valid(s.generated_at)) {
+                    Some(ScorecardLookup::Found(scorecard.clone()))
+                } else {
+                    cache
+                        .not_found
+                        .get(&url)
+                        .filter(|ts| is_valid(**ts))
+                        .map(|ts| ScorecardLookup::NotFound(*ts))
+                }
+            }) {
+                // Use cached data when available if it hasn't expired yet
+                (url, Ok(cached_scorecard))
+            } else {
+                // Otherwise we pull it from the Scorecard API
+                let _permit = concurrency::acquire().await;
+                let result = Scorecard::new(sc.clone(), &url).await;
+                metrics::record_external_data_fetch("scorecard", result.is_ok());
+                let result = result.map(|scorecard| match scorecard {
+                    Some(scorecard) => ScorecardLookup::Found(scorecard),
+                    None => ScorecardLookup::NotFound(Utc::now()),
+                });
+                (url.clone(), result)
+            }
+        })
+        .buffer_unordered(SCORECARD_MAX_CONCURRENCY)
+        .collect()
+        .await;
+    let mut data = CachedScorecardData::default();
+    for (url, result) in results {
+        match result {
+            Ok(ScorecardLookup::Found(scorecard)) => {
+                data.scorecards.insert(url, scorecard);
+            }
+            Ok(ScorecardLookup::NotFound(ts)) => {
+                data.not_found.insert(url, ts);
+            }
+            Err(err) => {
+                let msg = format!("error collecting scorecard data for {url}: {err}");
+                warnings::add(WarningKind::ExternalData, None, msg);
+            }
+        }
+    }
+
+    // Write data (in json format) to cache
+    cache.write(SCORECARD_CACHE_FILE, &serde_json::to_vec_pretty(&data)?).await?;
+
+    Ok(data.scorecards)
+}
+
+/// Scorecard data stored in the cache.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct CachedScorecardData {
+    scorecards: ScorecardData,
+
+    /// Repositories the Scorecard API has no results for, along with the time
+    /// they were requested.
+    not_found: HashMap<RepositoryUrl, DateTime<Utc>>,
+}
+
+/// Result of looking up the scorecard of a repository.
+enum ScorecardLookup {
+    Found(Scorecard),
+    NotFound(DateTime<Utc>),
+}
+
+/// Type alias to represent some repositories' Scorecard data.
+pub(crate) type ScorecardData = HashMap<RepositoryUrl, Scorecard>;
+
+/// Type alias to represent a repository url.
+type RepositoryUrl = String;
+
+/// OpenSSF Scorecard results of a repository.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Scorecard {
+    /// Score for each of the checks (-1 when the check is not applicable).
+    pub checks: BTreeMap<String, i64>,
+    pub generated_at: DateTime<Utc>,
+    pub score: f64,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+}
+
+impl Scorecard {
+    /// Create a new Scorecard instance from information obtained from the
+    /// Scorecard API (if it has any results for the repository provided).
+    async fn new(sc: DynSC, repo_url: &str) -> Result<Option<Self>> {
+        let (owner, repo) = get_owner_and_repo(repo_url, GITHUB_HOST)?;
+        let project = format!("{GITHUB_HOST}/{owner}/{repo}");
+        let Some(sc_result) = sc.get_scorecard(&project).await? else {
+            return Ok(None);
+        };
+
+        Ok(Some(Scorecard {
+            checks: sc_result.checks.into_iter().map(|check| (check.name, check.score)).collect(),
+            generated_at: Utc::now(),
+            score: sc_result.score,
+            date: sc_result.date,
+        }))
+    }
+}
+
+/// Scorecard API base url.
+const SCORECARD_API_URL: &str = "https://api.securityscorecards.dev";
+
+/// Type alias to represent a SC trait object.
+type DynSC = Arc<dyn SC + Send + Sync>;
+
+/// Trait that defines some operations a SC implementation must support.
+#[async_trait]
+#[cfg_attr(test, automock)]
+trait SC {
+    /// Get the scorecard results of the project provided (if available).
+    async fn get_scorecard(&self, project: &str) -> Result<Option<SCResult>>;
+}
+
+/// SC implementation backed by the Scorecard API.
+struct SCApi {
+    http_client: reqwest::Client,
+}
+
+impl SCApi {
+    /// Create a new SCApi instance.
+    fn new() -> Result<Self> {
+        let user_agent = format!("{}/{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+        let http_client = reqwest::Client::builder().user_agent(user_agent).build()?;
+
+        Ok(Self { http_client })
+    }
+}
+
+#[async_trait]
+impl SC for SCApi {
+    /// [SC::get_scorecard]
+    #[instrument(fields(?project), skip_all, err)]
+    async fn get_scorecard(&self, project: &str) -> Result<Option<SCResult>> {
+        let url = format!("{SCORECARD_API_URL}/projects/{project}");
+        let response = self.http_client.get(url).send().await?;
+        match response.status() {
+            StatusCode::OK => {}
+            StatusCode::NOT_FOUND => return Ok(None),
+            status => return Err(format_err!("unexpected status code: {status:?}")),
+        }
+        let result: SCResult = response.json().await?;
+        Ok(Some(result))
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct SCResult {
+    checks: Vec<SCCheck>,
+    date: Option<String>,
+    score: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct SCCheck {
+    name: String,
+    score: i64,
+}
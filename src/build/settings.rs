@@ -10,23 +10,32 @@
 use super::data::{Category, CategoryName};
 use crate::SettingsSource;
 use anyhow::{format_err, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
 use reqwest::StatusCode;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::{fs, path::Path};
+use std::{collections::BTreeMap, env, fs, path::Path};
 use tracing::{debug, instrument};
 
 /// Landscape settings.
-#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub(crate) struct LandscapeSettings {
     pub foundation: String,
     pub images: Images,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_items_fields: Option<Vec<String>>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub categories: Option<Vec<Category>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub colors: Option<Colors>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_code: Option<CustomCode>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub featured_items: Option<Vec<FeaturedItemRule>>,
 
@@ -36,11 +45,32 @@ pub(crate) struct LandscapeSettings {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub groups: Option<Vec<Group>>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hosting_rules: Option<HostingRules>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub html_sanitization: Option<HtmlSanitization>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maturity_levels: Option<Vec<String>>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub members_category: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organizations_legal_suffixes: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redacted_fields: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub robots: Option<Robots>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub social_networks: Option<SocialNetworks>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<Tag>>,
 }
 
 impl LandscapeSettings {
@@ -65,7 +95,7 @@ impl LandscapeSettings {
     /// Create a new landscape settings instance from the file provided.
     fn new_from_file(file: &Path) -> Result<Self> {
         let raw_data = fs::read_to_string(file)?;
-        let settings: LandscapeSettings = serde_yaml::from_str(&raw_data)?;
+        let settings = LandscapeSettings::new_from_yaml(&raw_data)?;
 
         Ok(settings)
     }
@@ -80,14 +110,142 @@ impl LandscapeSettings {
             ));
         }
         let raw_data = resp.text().await?;
-        let settings: LandscapeSettings = serde_yaml::from_str(&raw_data)?;
+        let settings = LandscapeSettings::new_from_yaml(&raw_data)?;
 
         Ok(settings)
     }
+
+    /// Create a new landscape settings instance from the YAML string provided.
+    /// Environment variables referenced in string values (`${VAR}`) will be
+    /// interpolated.
+    fn new_from_yaml(s: &str) -> Result<Self> {
+        let mut value: serde_yaml::Value = serde_yaml::from_str(s)?;
+        interpolate_env_vars(&mut value)?;
+        let settings: LandscapeSettings =
+            serde_path_to_error::deserialize(value).map_err(|err| format_err!("invalid settings: {err}"))?;
+        settings.validate()?;
+
+        Ok(settings)
+    }
+
+    /// Check that the colors defined in the settings are valid CSS colors and
+    /// that the enum-like fields contain one of the values allowed. All the
+    /// invalid settings found are reported, along with their path.
+    fn validate(&self) -> Result<()> {
+        let mut errors = vec![];
+
+        // Colors
+        let mut check_color = |path: String, color: &str| {
+            if csscolorparser::parse(color).is_err() {
+                errors.push(format!("- {path}: invalid color ({color})"));
+            }
+        };
+        if let Some(colors) = &self.colors {
+            for (name, color) in [
+                ("color1", &colors.color1),
+                ("color2", &colors.color2),
+                ("color3", &colors.color3),
+                ("color4", &colors.color4),
+                ("color5", &colors.color5),
+                ("color6", &colors.color6),
+            ] {
+                check_color(format!("colors.{name}"), color);
+            }
+        }
+        for (i, tag) in self.tags.iter().flatten().enumerate() {
+            if let Some(color) = &tag.color {
+                check_color(format!("tags[{i}].color"), color);
+            }
+        }
+
+        // Featured items rules fields
+        for (i, rule) in self.featured_items.iter().flatten().enumerate() {
+            if !FEATURED_ITEMS_RULE_FIELDS.contains(&rule.field.as_str()) {
+                errors.push(format!(
+                    "- featured_items[{i}].field: invalid value ({}), expecting one of: {}",
+                    rule.field,
+                    FEATURED_ITEMS_RULE_FIELDS.join(", ")
+                ));
+            }
+        }
+
+        // HTML sanitization allowed tags
+        if let Some(html_sanitization) = &self.html_sanitization {
+            for (i, tag) in html_sanitization.allowed_tags.iter().flatten().enumerate() {
+                if HTML_SANITIZATION_UNSUPPORTED_TAGS.contains(&tag.as_str()) {
+                    errors.push(format!(
+                        "- html_sanitization.allowed_tags[{i}]: unsupported tag ({tag})"
+                    ));
+                }
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(format_err!("invalid settings:\n{}", errors.join("\n")));
+        }
+
+        Ok(())
+    }
+}
+
+/// Interpolate the environment variables referenced in the string values
+/// found in the YAML value provided. Variables can be referenced using the
+/// `${VAR}` syntax, or `${VAR:-default}` to provide a default value that will
+/// be used when the variable is not defined (or empty). The custom code
+/// snippets are left untouched, as they may contain similar constructs (i.e.
+/// JavaScript template literals).
+fn interpolate_env_vars(value: &mut serde_yaml::Value) -> Result<()> {
+    match value {
+        serde_yaml::Value::String(s) => {
+            let mut result = String::with_capacity(s.len());
+            let mut last_match_end = 0;
+            for c in ENV_VAR.captures_iter(s) {
+                let m = c.get(0).expect("match to be available");
+                result.push_str(&s[last_match_end..m.start()]);
+                let name = &c["name"];
+                match (env::var(name), c.name("default")) {
+                    (Ok(var_value), _) if !var_value.is_empty() => result.push_str(&var_value),
+                    (_, Some(default)) => result.push_str(default.as_str()),
+                    (Ok(_), None) => {}
+                    (Err(_), None) => {
+                        return Err(format_err!(
+                            "environment variable referenced in settings not defined: {name}"
+                        ))
+                    }
+                }
+                last_match_end = m.end();
+            }
+            result.push_str(&s[last_match_end..]);
+            *s = result;
+        }
+        serde_yaml::Value::Sequence(seq) => {
+            for v in seq {
+                interpolate_env_vars(v)?;
+            }
+        }
+        serde_yaml::Value::Mapping(map) => {
+            for (k, v) in &mut *map {
+                if k.as_str() == Some("custom_code") {
+                    continue;
+                }
+                interpolate_env_vars(v)?;
+            }
+        }
+        serde_yaml::Value::Tagged(tagged) => interpolate_env_vars(&mut tagged.value)?,
+        _ => {}
+    }
+
+    Ok(())
+}
+
+lazy_static! {
+    /// Environment variable reference regular expression.
+    static ref ENV_VAR: Regex = Regex::new(r"\$\{(?P<name>[A-Za-z_][A-Za-z0-9_]*)(:-(?P<default>[^}]*))?\}")
+        .expect("exprs in ENV_VAR to be valid");
 }
 
 /// Colors used across the landscape UI.
-#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub(crate) struct Colors {
     pub color1: String,
     pub color2: String,
@@ -97,17 +255,48 @@ pub(crate) struct Colors {
     pub color6: String,
 }
 
+impl Default for Colors {
+    /// Colors used by default in the web application.
+    fn default() -> Self {
+        Self {
+            color1: "rgba(0, 107, 204, 1)".to_string(),
+            color2: "rgba(255, 0, 170, 1)".to_string(),
+            color3: "rgba(96, 149, 214, 1)".to_string(),
+            color4: "rgba(0, 42, 81, 0.7)".to_string(),
+            color5: "rgba(19, 17, 24, 0.7)".to_string(),
+            color6: "rgba(80, 142, 191, 0.7)".to_string(),
+        }
+    }
+}
+
+/// Custom code snippets (i.e. analytics scripts or styles) injected in the
+/// index document. They are added as is, without any sanitization, so only
+/// trusted content should be used here.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub(crate) struct CustomCode {
+    /// Snippet added at the end of the head section.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub head: Option<String>,
+
+    /// Snippet added at the end of the body section.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+/// Items fields that can be used in featured items rules.
+const FEATURED_ITEMS_RULE_FIELDS: [&str; 2] = ["maturity", "subcategory"];
+
 /// Featured item rule information. A featured item is specially highlighted in
 /// the web application, usually making it larger with some special styling.
 /// These rules are used to decide which items should be featured.
-#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub(crate) struct FeaturedItemRule {
     pub field: String,
     pub options: Vec<FeaturedItemRuleOption>,
 }
 
 /// Featured item rule option.
-#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub(crate) struct FeaturedItemRuleOption {
     pub value: String,
 
@@ -116,10 +305,13 @@ pub(crate) struct FeaturedItemRuleOption {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub order: Option<usize>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
 }
 
 /// Grid items size.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub(crate) enum GridItemsSize {
     Small,
@@ -129,14 +321,84 @@ pub(crate) enum GridItemsSize {
 
 /// Landscape group. A group provides a mechanism to organize sets of
 /// categories in the web application.
-#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub(crate) struct Group {
     pub name: String,
     pub categories: Vec<CategoryName>,
 }
 
+/// Hosting rules. These rules are used to generate the `_redirects` and
+/// `_headers` files supported by some hosting platforms (i.e. Netlify or
+/// Cloudflare Pages).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub(crate) struct HostingRules {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<Vec<HeadersRule>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redirects: Option<Vec<RedirectRule>>,
+}
+
+/// Headers rule. Headers will be applied to the paths matching the pattern.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub(crate) struct HeadersRule {
+    pub path: String,
+    pub headers: BTreeMap<String, String>,
+}
+
+/// Redirect rule.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub(crate) struct RedirectRule {
+    pub from: String,
+    pub to: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+}
+
+/// Tags that cannot be added to the HTML sanitization allowlist (they are not
+/// supported by the sanitizer).
+const HTML_SANITIZATION_UNSUPPORTED_TAGS: [&str; 2] = ["animate", "set"];
+
+/// HTML sanitization options. The HTML generated from the guide content is
+/// always sanitized using a safe allowlist of tags and attributes. These
+/// options allow extending that allowlist (i.e. to permit iframes).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub(crate) struct HtmlSanitization {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_tags: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_tag_attributes: Option<BTreeMap<String, Vec<String>>>,
+}
+
+impl HtmlSanitization {
+    /// Create a new HTML sanitizer that extends the default allowlist with
+    /// the tags and attributes provided.
+    pub(crate) fn sanitizer(&self) -> ammonia::Builder<'_> {
+        let mut sanitizer = ammonia::Builder::default();
+        if let Some(tags) = &self.allowed_tags {
+            sanitizer.add_tags(tags.iter().map(String::as_str));
+            sanitizer.rm_clean_content_tags(tags.iter().map(String::as_str));
+        }
+        if let Some(tag_attributes) = &self.allowed_tag_attributes {
+            for (tag, attributes) in tag_attributes {
+                sanitizer.add_tag_attributes(tag.as_str(), attributes.iter().map(String::as_str));
+                sanitizer.rm_clean_content_tags([tag.as_str()]);
+
+                // The links rel attribute is managed by the sanitizer unless
+                // it has been explicitly allowed
+                if tag == "a" && attributes.iter().any(|attribute| attribute == "rel") {
+                    sanitizer.link_rel(None);
+                }
+            }
+        }
+        sanitizer
+    }
+}
+
 /// Images urls.
-#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub(crate) struct Images {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub favicon: Option<String>,
@@ -144,15 +406,34 @@ pub(crate) struct Images {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub footer_logo: Option<String>,
 
+    #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub footer_logo_srcset: Option<String>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub header_logo: Option<String>,
 
+    #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub header_logo_srcset: Option<String>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub open_graph: Option<String>,
 }
 
+/// Robots rules. These rules are used to generate the `robots.txt` file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub(crate) struct Robots {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disallow: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sitemap_url: Option<String>,
+}
+
 /// Social networks urls.
-#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub(crate) struct SocialNetworks {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub facebook: Option<String>,
@@ -184,3 +465,16 @@ pub(crate) struct SocialNetworks {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub youtube: Option<String>,
 }
+
+/// Tag definition. Tags provide a mechanism to group items independently of
+/// the categories they belong to.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub(crate) struct Tag {
+    pub name: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+}
@@ -0,0 +1,133 @@
+//! This module provides some helper functions used to generate a single file
+//! version of the landscape website, where the web application assets, the
+//! datasets and the logos are inlined in the index document.
+
+use super::{datasets::Datasets, WebAssets};
+use anyhow::Result;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use std::{collections::HashMap, fs, path::Path};
+use url::Url;
+
+lazy_static! {
+    /// Regular expression used to find the external scripts in the index document.
+    static ref HTML_SCRIPT: Regex = Regex::new(r#"<script\s[^>]*src="(?P<src>[^"]+)"[^>]*>\s*</script>"#)
+        .expect("expr in HTML_SCRIPT to be valid");
+
+    /// Regular expression used to find the external stylesheets in the index document.
+    static ref HTML_STYLESHEET: Regex = Regex::new(r#"<link\s[^>]*rel="stylesheet"[^>]*>"#)
+        .expect("expr in HTML_STYLESHEET to be valid");
+
+    /// Regular expression used to extract the href attribute of an HTML tag.
+    static ref HTML_HREF: Regex = Regex::new(r#"href="(?P<href>[^"]+)""#).expect("expr in HTML_HREF to be valid");
+}
+
+/// Replace the logos and images referenced from the datasets provided with
+/// data URIs, so that they don't need to be fetched from the output directory.
+pub(crate) fn inline_datasets_files(datasets: &mut Datasets, output_dir: &Path) -> Result<()> {
+    let mut data_uris: HashMap<String, String> = HashMap::new();
+    let mut inline = |path: &mut String| -> Result<()> {
+        // Images hosted externally are kept as they are
+        if path.is_empty() || Url::parse(path).is_ok() {
+            return Ok(());
+        }
+        if !data_uris.contains_key(path.as_str()) {
+            let data = fs::read(output_dir.join(path.as_str()))?;
+            data_uris.insert(path.clone(), data_uri(path, &data));
+        }
+        *path = data_uris[path.as_str()].clone();
+        Ok(())
+    };
+
+    // Settings images (the open graph image is not inlined as it is only
+    // used by external services)
+    let images = &mut datasets.base.images;
+    for image in [
+        &mut images.favicon,
+        &mut images.footer_logo,
+        &mut images.header_logo,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        inline(image)?;
+    }
+
+    // The logos variants for hi-DPI displays are not inlined
+    images.footer_logo_srcset = None;
+    images.header_logo_srcset = None;
+
+    // Items logos
+    for item in &mut datasets.base.items {
+        inline(&mut item.logo)?;
+    }
+    for item in &mut datasets.full.items {
+        inline(&mut item.logo)?;
+    }
+
+    Ok(())
+}
+
+/// Inline the web application scripts and stylesheets referenced from the
+/// index document provided. Any other web assets referenced from them (i.e.
+/// images or fonts) are inlined as data URIs.
+pub(crate) fn inline_web_assets(index: &str) -> String {
+    // Prepare data URIs for the web assets that are not scripts or stylesheets
+    let mut data_uris = vec![];
+    for asset_path in WebAssets::iter() {
+        if asset_path == "index.html" || asset_path.ends_with(".js") || asset_path.ends_with(".css") {
+            continue;
+        }
+        if let Some(embedded_file) = WebAssets::get(&asset_path) {
+            data_uris.push((asset_path.to_string(), data_uri(&asset_path, &embedded_file.data)));
+        }
+    }
+
+    // Helper closure to get the content of a script or stylesheet, replacing
+    // the references to other assets with their data URI
+    let get_asset = |path: &str| -> Option<String> {
+        let path = path.trim_start_matches("./").trim_start_matches('/');
+        let embedded_file = WebAssets::get(path)?;
+        let mut content = String::from_utf8_lossy(&embedded_file.data).into_owned();
+        for (asset_path, data_uri) in &data_uris {
+            let file_name = asset_path.rsplit('/').next().unwrap_or(asset_path);
+            for reference in [
+                format!("./{asset_path}"),
+                format!("/{asset_path}"),
+                asset_path.clone(),
+                format!("./{file_name}"),
+                file_name.to_string(),
+            ] {
+                content = content.replace(&reference, data_uri);
+            }
+        }
+        Some(content)
+    };
+
+    // Inline scripts
+    let index = HTML_SCRIPT.replace_all(index, |c: &Captures| match get_asset(&c["src"]) {
+        Some(script) => format!(
+            r#"<script type="module">{}</script>"#,
+            script.replace("</script", r"<\/script")
+        ),
+        None => c[0].to_string(),
+    });
+
+    // Inline stylesheets
+    let index = HTML_STYLESHEET.replace_all(&index, |c: &Captures| {
+        let stylesheet = HTML_HREF.captures(&c[0]).and_then(|href| get_asset(&href["href"]));
+        match stylesheet {
+            Some(stylesheet) => format!("<style>{stylesheet}</style>"),
+            None => c[0].to_string(),
+        }
+    });
+
+    index.into_owned()
+}
+
+/// Return a data URI containing the data provided.
+pub(crate) fn data_uri(path: &str, data: &[u8]) -> String {
+    let mime_type = mime_guess::from_path(path).first_or_octet_stream();
+    format!("data:{mime_type};base64,{}", STANDARD.encode(data))
+}
@@ -0,0 +1,239 @@
+//! This module defines some types used to represent the social networks data
+//! collected for the landscape items (like the number of followers of their
+//! Bluesky or Mastodon accounts), as well as the functionality used to collect
+//! it.
+
+use super::{
+    cache::DynCache,
+    concurrency, metrics, refresh,
+    warnings::{self, WarningKind},
+    LandscapeData,
+};
+use anyhow::{format_err, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use lazy_static::lazy_static;
+#[cfg(test)]
+use mockall::automock;
+use regex::Regex;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::Arc};
+use tracing::{debug, instrument};
+
+/// File used to cache data collected from the social networks APIs.
+const SOCIAL_CACHE_FILE: &str = "social.json";
+
+/// How long the social networks data in the cache is valid (in days).
+const SOCIAL_CACHE_TTL: i64 = 7;
+
+/// Maximum number of concurrent requests to the social networks APIs.
+const SOCIAL_MAX_CONCURRENCY: usize = 10;
+
+/// Social networks supported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SocialNetwork {
+    Bluesky,
+    Mastodon,
+}
+
+/// Collect the number of followers of the items accounts in the social
+/// networks provided, reusing cached data whenever possible.
+#[instrument(skip_all, err)]
+pub(crate) async fn collect_social_data(
+    cache: &DynCache,
+    landscape_data: &LandscapeData,
+    networks: &[SocialNetwork],
+) -> Result<SocialData> {
+    if networks.is_empty() {
+        return Ok(SocialData::default());
+    }
+    debug!(
+        ?networks,
+        "collecting social networks data (this may take a while)"
+    );
+
+    // Read cached data (if available)
+    let mut cached_data: Option<SocialData> = None;
+    if let Ok(Some(json_data)) = cache.read(SOCIAL_CACHE_FILE).await {
+        if let Ok(social_data) = serde_json::from_slice(&json_data) {
+            cached_data = Some(social_data);
+        }
+    }
+
+    // Setup social networks API client
+    let sn: DynSN = Arc::new(SNApi::new()?);
+
+    // Collect urls of the accounts to process
+    let mut urls = vec![];
+    for item in &landscape_data.items {
+        if networks.contains(&SocialNetwork::Bluesky) {
+            if let Some(url) = &item.bluesky_url {
+                urls.push(url);
+            }
+        }
+        if networks.contains(&SocialNetwork::Mastodon) {
+            if let Some(url) = &item.mastodon_url {
+                urls.push(url);
+            }
+        }
+    }
+    urls.sort();
+    urls.dedup();
+
+    // Collect accounts data, reusing cached data when available
+    let social_data: SocialData = stream::iter(urls)
+        .map(|url| async {
+            let url = url.clone();
+            if let Some(cached_account) = cached_data.as_ref().and_then(|cache| {
+                cache.get(&url).and_then(|account| {
+                    if refresh::is_cached_data_valid(&url, account.generated_at, SOCIAL_CACHE_TTL) {
+                        Some(account)
+                    } else {
+                        None
+                    }
+                })
+            }) {
+                // Use cached data when available if it hasn't expired yet
+                (url, Ok(cached_account.clone()))
+            } else {
+                // Otherwise we pull it from the corresponding API
+                let _permit = concurrency::acquire().await;
+                let result = SocialAccount::new(sn.clone(), &url).await;
+                metrics::record_external_data_fetch("social", result.is_ok());
+                (url.clone(), result)
+            }
+        })
+        .buffer_unordered(SOCIAL_MAX_CONCURRENCY)
+        .collect::<HashMap<String, Result<SocialAccount>>>()
+        .await
+        .into_iter()
+        .filter_map(|(url, result)| match result {
+            Ok(account) => Some((url, account)),
+            Err(err) => {
+                let msg = format!("error collecting social data for {url}: {err}");
+                warnings::add(WarningKind::ExternalData, None, msg);
+                None
+            }
+        })
+        .collect();
+
+    // Write data (in json format) to cache
+    cache.write(SOCIAL_CACHE_FILE, &serde_json::to_vec_pretty(&social_data)?).await?;
+
+    Ok(social_data)
+}
+
+/// Type alias to represent some social networks accounts data.
+pub(crate) type SocialData = HashMap<AccountUrl, SocialAccount>;
+
+/// Type alias to represent a social network account url.
+type AccountUrl = String;
+
+/// Social network account information.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct SocialAccount {
+    pub followers: i64,
+    pub generated_at: DateTime<Utc>,
+}
+
+impl SocialAccount {
+    /// Create a new SocialAccount instance from information obtained from the
+    /// API of the social network the account url provided belongs to.
+    async fn new(sn: DynSN, url: &str) -> Result<Self> {
+        let followers = if let Some(c) = BLUESKY_URL.captures(url) {
+            sn.get_bluesky_profile(&c["handle"]).await?.followers_count
+        } else if let Some(c) = MASTODON_URL.captures(url) {
+            sn.get_mastodon_account(&c["instance"], &c["username"]).await?.followers_count
+        } else {
+            return Err(format_err!("invalid social network account url"));
+        };
+
+        Ok(SocialAccount {
+            followers,
+            generated_at: Utc::now(),
+        })
+    }
+}
+
+/// Bluesky public API base url.
+const BLUESKY_API_URL: &str = "https://public.api.bsky.app";
+
+/// Type alias to represent a SN trait object.
+type DynSN = Arc<dyn SN + Send + Sync>;
+
+/// Trait that defines some operations a SN implementation must support.
+#[async_trait]
+#[cfg_attr(test, automock)]
+trait SN {
+    /// Get the Bluesky profile of the handle provided.
+    async fn get_bluesky_profile(&self, handle: &str) -> Result<BlueskyProfile>;
+
+    /// Get the Mastodon account of the user provided in the given instance.
+    async fn get_mastodon_account(&self, instance: &str, username: &str) -> Result<MastodonAccount>;
+}
+
+/// SN implementation backed by the social networks public APIs.
+struct SNApi {
+    http_client: reqwest::Client,
+}
+
+impl SNApi {
+    /// Create a new SNApi instance.
+    fn new() -> Result<Self> {
+        let user_agent = format!("{}/{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+        let http_client = reqwest::Client::builder().user_agent(user_agent).build()?;
+
+        Ok(Self { http_client })
+    }
+
+    /// Send a GET request to the url provided and deserialize the response.
+    async fn get<T: serde::de::DeserializeOwned>(&self, url: &str, query: &[(&str, &str)]) -> Result<T> {
+        let response = self.http_client.get(url).query(query).send().await?;
+        if response.status() != StatusCode::OK {
+            return Err(format_err!("unexpected status code: {:?}", response.status()));
+        }
+        Ok(response.json().await?)
+    }
+}
+
+#[async_trait]
+impl SN for SNApi {
+    /// [SN::get_bluesky_profile]
+    #[instrument(fields(?handle), skip_all, err)]
+    async fn get_bluesky_profile(&self, handle: &str) -> Result<BlueskyProfile> {
+        let url = format!("{BLUESKY_API_URL}/xrpc/app.bsky.actor.getProfile");
+        self.get(&url, &[("actor", handle)]).await
+    }
+
+    /// [SN::get_mastodon_account]
+    #[instrument(fields(?instance, ?username), skip_all, err)]
+    async fn get_mastodon_account(&self, instance: &str, username: &str) -> Result<MastodonAccount> {
+        let url = format!("https://{instance}/api/v1/accounts/lookup");
+        self.get(&url, &[("acct", username)]).await
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BlueskyProfile {
+    followers_count: i64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct MastodonAccount {
+    followers_count: i64,
+}
+
+lazy_static! {
+    /// Bluesky profile url regular expression.
+    pub(crate) static ref BLUESKY_URL: Regex =
+        Regex::new("^https://bsky.app/profile/(?P<handle>[^/]+)/?$")
+            .expect("exprs in BLUESKY_URL to be valid");
+
+    /// Mastodon account url regular expression.
+    pub(crate) static ref MASTODON_URL: Regex =
+        Regex::new("^https://(?P<instance>[^/]+)/@(?P<username>[^/@]+)/?$")
+            .expect("exprs in MASTODON_URL to be valid");
+}
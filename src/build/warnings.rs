@@ -0,0 +1,139 @@
+//! This module provides a mechanism to collect the warnings produced while
+//! processing a landscape, so that they can be reported all together at the
+//! end (and promoted to errors when running in strict mode).
+
+use anyhow::{format_err, Result};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt, fmt::Write, fs, path::Path, sync::Mutex};
+
+lazy_static! {
+    /// Warnings collected so far.
+    static ref WARNINGS: Mutex<Vec<Warning>> = Mutex::new(vec![]);
+}
+
+/// Warning information.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Warning {
+    pub kind: WarningKind,
+    pub message: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item: Option<String>,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] ", self.kind)?;
+        if let Some(item) = &self.item {
+            write!(f, "{item}: ")?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Kind of warning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum WarningKind {
+    Accessibility,
+    ExternalData,
+    Guide,
+    ImplausibleData,
+    Logo,
+    LowStars,
+    Settings,
+    SettingsImage,
+}
+
+impl fmt::Display for WarningKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            WarningKind::Accessibility => "accessibility",
+            WarningKind::ExternalData => "external_data",
+            WarningKind::Guide => "guide",
+            WarningKind::ImplausibleData => "implausible_data",
+            WarningKind::Logo => "logo",
+            WarningKind::LowStars => "low_stars",
+            WarningKind::Settings => "settings",
+            WarningKind::SettingsImage => "settings_image",
+        };
+        write!(f, "{kind}")
+    }
+}
+
+/// Add a warning to the collection.
+pub(crate) fn add(kind: WarningKind, item: Option<&str>, message: impl Into<String>) {
+    let warning = Warning {
+        kind,
+        message: message.into(),
+        item: item.map(ToString::to_string),
+    };
+    WARNINGS.lock().expect("warnings lock not to be poisoned").push(warning);
+}
+
+/// Write a summary of the warnings collected so far to the output directory,
+/// grouped by kind and item, both in JSON (warnings.json) and plain text
+/// (warnings.txt) formats.
+pub(crate) fn write_summary_files(output_dir: &Path) -> Result<()> {
+    let warnings = WARNINGS.lock().expect("warnings lock not to be poisoned").clone();
+
+    // Group warnings by kind and item (warnings not related to any item use
+    // an empty item name)
+    let mut summary: BTreeMap<WarningKind, BTreeMap<String, Vec<String>>> = BTreeMap::new();
+    for warning in warnings {
+        summary
+            .entry(warning.kind)
+            .or_default()
+            .entry(warning.item.unwrap_or_default())
+            .or_default()
+            .push(warning.message);
+    }
+
+    // warnings.json
+    fs::write(
+        output_dir.join("warnings.json"),
+        serde_json::to_vec_pretty(&summary)?,
+    )?;
+
+    // warnings.txt
+    let mut txt = String::new();
+    for (kind, items) in &summary {
+        _ = writeln!(txt, "[{kind}]");
+        for (item, messages) in items {
+            for message in messages {
+                if item.is_empty() {
+                    _ = writeln!(txt, "- {message}");
+                } else {
+                    _ = writeln!(txt, "- {item}: {message}");
+                }
+            }
+        }
+        txt.push('\n');
+    }
+    fs::write(output_dir.join("warnings.txt"), txt.trim_end().to_string() + "\n")?;
+
+    Ok(())
+}
+
+/// Report the warnings collected so far, returning an error if any were found
+/// when running in strict mode.
+pub(crate) fn report(strict: bool) -> Result<()> {
+    let warnings = std::mem::take(&mut *WARNINGS.lock().expect("warnings lock not to be poisoned"));
+    if warnings.is_empty() {
+        return Ok(());
+    }
+
+    let mut summary = format!("{} warning(s) found:\n", warnings.len());
+    for warning in &warnings {
+        _ = writeln!(summary, "- {warning}");
+    }
+    if strict {
+        return Err(format_err!(
+            "{summary}(warnings are treated as errors in strict mode)"
+        ));
+    }
+    eprint!("{summary}");
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+//! This module provides the functionality used to rebuild the landscape when
+//! any of the local sources provided changes (watch mode).
+
+use super::{build, warnings};
+use crate::BuildArgs;
+use anyhow::{format_err, Result};
+use notify::{RecursiveMode, Watcher};
+use std::{path::PathBuf, time::Duration};
+use tokio::sync::mpsc;
+use tracing::{error, info, instrument};
+
+/// Time to wait for more changes before triggering a rebuild.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Build the landscape website and rebuild it every time any of the local
+/// sources (data file, settings file, guide file or logos directory) changes.
+/// Build errors are logged, and the sources will continue being watched.
+#[instrument(skip_all, err)]
+pub(crate) async fn watch(args: &BuildArgs) -> Result<()> {
+    // Collect local paths to watch
+    let paths: Vec<(PathBuf, RecursiveMode)> = [
+        (&args.data_source.data_file, RecursiveMode::NonRecursive),
+        (&args.settings_source.settings_file, RecursiveMode::NonRecursive),
+        (&args.guide_source.guide_file, RecursiveMode::NonRecursive),
+        (&args.logos_source.logos_path, RecursiveMode::Recursive),
+        (&args.bundle, RecursiveMode::NonRecursive),
+    ]
+    .into_iter()
+    .filter_map(|(path, mode)| path.clone().map(|path| (path, mode)))
+    .collect();
+    if paths.is_empty() {
+        return Err(format_err!("watch mode requires some local sources to watch"));
+    }
+
+    // Setup watcher, signaling the file system events through the channel.
+    // Events are coalesced: when a signal is already pending (i.e. during a
+    // rebuild) new ones are discarded, so the watcher thread never blocks
+    let (tx, mut rx) = mpsc::channel(1);
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if event.is_ok_and(|event| !event.kind.is_access()) {
+            _ = tx.try_send(());
+        }
+    })?;
+    for (path, mode) in &paths {
+        watcher.watch(path, *mode)?;
+    }
+
+    loop {
+        // Build landscape, reporting the warnings collected
+        if let Err(err) = build(args, false).await {
+            error!(?err, "error building landscape website");
+        }
+        if let Err(err) = warnings::report(false) {
+            error!(?err, "error reporting warnings");
+        }
+        info!("watching for changes..");
+
+        // Wait for changes, waiting a bit for more changes to happen before
+        // triggering the rebuild
+        if rx.recv().await.is_none() {
+            return Ok(());
+        }
+        loop {
+            tokio::time::sleep(WATCH_DEBOUNCE).await;
+            let mut changed = false;
+            while rx.try_recv().is_ok() {
+                changed = true;
+            }
+            if !changed {
+                break;
+            }
+        }
+        info!("changes detected, rebuilding landscape website..");
+    }
+}
@@ -0,0 +1,202 @@
+//! This module defines the functionality of the check-links CLI subcommand.
+
+use crate::{build::LandscapeData, CheckLinksArgs, LinksReportFormat};
+use anyhow::{format_err, Context, Result};
+use futures::stream::{self, StreamExt};
+use reqwest::Url;
+use serde::Serialize;
+use std::{collections::BTreeMap, fmt::Write, time::Duration};
+use tracing::{debug, instrument};
+
+/// Check the links (homepages, repositories and logos urls) referenced from the
+/// landscape data are reachable, printing a report with the results. An error
+/// is returned if any of the links is broken.
+#[instrument(skip_all, err)]
+pub(crate) async fn check_links(args: &CheckLinksArgs) -> Result<()> {
+    if args.concurrency == 0 {
+        return Err(format_err!("the concurrency must be greater than zero"));
+    }
+
+    // Get landscape data from the source provided
+    let landscape_data = LandscapeData::new(&args.data_source)
+        .await
+        .context("the landscape data file provided is not valid")?;
+
+    // Collect the links to check, along with the items referencing them
+    let links = collect_links(&landscape_data, args.logos_url.as_deref());
+    debug!(links = links.len(), "checking links (this may take a while)");
+
+    // Check links
+    let user_agent = format!("{}/{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+    let http_client = reqwest::Client::builder()
+        .user_agent(user_agent)
+        .timeout(Duration::from_secs(args.timeout))
+        .build()?;
+    let mut results: Vec<LinkCheck> = stream::iter(links)
+        .map(|((url, kind), items)| {
+            let http_client = http_client.clone();
+            async move {
+                let status = check_link(&http_client, &url).await;
+                LinkCheck {
+                    url,
+                    kind,
+                    items,
+                    status,
+                }
+            }
+        })
+        .buffer_unordered(args.concurrency)
+        .collect()
+        .await;
+    results.sort_by(|a, b| a.url.cmp(&b.url).then(a.kind.cmp(&b.kind)));
+
+    // Print report
+    let report = match args.format {
+        LinksReportFormat::Json => serde_json::to_string_pretty(&results)?,
+        LinksReportFormat::Text => text_report(&results),
+    };
+    println!("{report}");
+
+    let broken = results.iter().filter(|r| r.status != LinkStatus::Ok).count();
+    if broken > 0 {
+        return Err(format_err!("{broken} broken link(s) found"));
+    }
+
+    Ok(())
+}
+
+/// Collect the links referenced from the landscape data, along with the names
+/// of the items referencing them. Logos are only checked when the base url
+/// where they are hosted is provided.
+fn collect_links(
+    landscape_data: &LandscapeData,
+    logos_url: Option<&str>,
+) -> BTreeMap<(String, LinkKind), Vec<String>> {
+    let mut links: BTreeMap<(String, LinkKind), Vec<String>> = BTreeMap::new();
+    for item in &landscape_data.items {
+        let mut add = |url: String, kind: LinkKind| {
+            links.entry((url, kind)).or_default().push(item.name.clone());
+        };
+
+        add(item.homepage_url.clone(), LinkKind::Homepage);
+        for repo in item.repositories.iter().flatten() {
+            add(repo.url.clone(), LinkKind::Repository);
+        }
+        if let Some(logos_url) = logos_url {
+            add(
+                format!("{}/{}", logos_url.trim_end_matches('/'), item.logo),
+                LinkKind::Logo,
+            );
+        }
+    }
+    links
+}
+
+/// Check the link provided is reachable.
+async fn check_link(http_client: &reqwest::Client, url: &str) -> LinkStatus {
+    match http_client.get(url).send().await {
+        Ok(resp) if resp.status().is_success() => LinkStatus::Ok,
+        Ok(resp) => LinkStatus::HttpError {
+            status_code: resp.status().as_u16(),
+        },
+        Err(err) if err.is_timeout() => LinkStatus::Timeout,
+        Err(err) if err.is_connect() => {
+            // Check if the host name can be resolved to tell DNS failures
+            // apart from other connection errors
+            if resolve_host(url).await {
+                LinkStatus::ConnectionError {
+                    error: err.to_string(),
+                }
+            } else {
+                LinkStatus::DnsFailure
+            }
+        }
+        Err(err) => LinkStatus::Error {
+            error: err.to_string(),
+        },
+    }
+}
+
+/// Check if the host of the url provided can be resolved.
+async fn resolve_host(url: &str) -> bool {
+    let Some((host, port)) = Url::parse(url)
+        .ok()
+        .and_then(|url| Some((url.host_str()?.to_string(), url.port_or_known_default()?)))
+    else {
+        return false;
+    };
+    tokio::net::lookup_host((host, port)).await.is_ok_and(|mut addrs| addrs.next().is_some())
+}
+
+/// Prepare a plain text report with the broken links found.
+fn text_report(results: &[LinkCheck]) -> String {
+    let mut report = String::new();
+    let broken: Vec<&LinkCheck> = results.iter().filter(|r| r.status != LinkStatus::Ok).collect();
+    for result in &broken {
+        let status = match &result.status {
+            LinkStatus::Ok => "ok".to_string(),
+            LinkStatus::HttpError { status_code } => format!("http error ({status_code})"),
+            LinkStatus::Timeout => "timeout".to_string(),
+            LinkStatus::DnsFailure => "dns failure".to_string(),
+            LinkStatus::ConnectionError { error } => format!("connection error ({error})"),
+            LinkStatus::Error { error } => format!("error ({error})"),
+        };
+        _ = writeln!(
+            report,
+            "- [{}] {} ({}): {status}",
+            result.kind,
+            result.url,
+            result.items.join(", ")
+        );
+    }
+    _ = write!(
+        report,
+        "{} link(s) checked, {} broken",
+        results.len(),
+        broken.len()
+    );
+    report
+}
+
+/// Result of checking a link.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct LinkCheck {
+    url: String,
+    kind: LinkKind,
+    items: Vec<String>,
+
+    #[serde(flatten)]
+    status: LinkStatus,
+}
+
+/// Kind of link.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum LinkKind {
+    Homepage,
+    Logo,
+    Repository,
+}
+
+impl std::fmt::Display for LinkKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self {
+            LinkKind::Homepage => "homepage",
+            LinkKind::Logo => "logo",
+            LinkKind::Repository => "repository",
+        };
+        write!(f, "{kind}")
+    }
+}
+
+/// Status of a link.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum LinkStatus {
+    Ok,
+    HttpError { status_code: u16 },
+    Timeout,
+    DnsFailure,
+    ConnectionError { error: String },
+    Error { error: String },
+}
@@ -2,14 +2,21 @@
 #![allow(clippy::doc_markdown)]
 
 use anyhow::Result;
-use build::build;
-use clap::{Args, Parser, Subcommand};
+use build::{build, preflight, warnings, watch};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use deploy::s3;
+use links::check_links;
+use new::new_landscape;
+use query::query_data;
 use std::path::PathBuf;
-use validate::validate_data;
+use validate::{validate_data, validate_settings};
 
 mod build;
 mod deploy;
+mod links;
+mod new;
+mod query;
+mod schema;
 mod validate;
 
 /// CLI arguments.
@@ -18,47 +25,376 @@ mod validate;
 struct Cli {
     #[command(subcommand)]
     command: Command,
+
+    /// Treat warnings as errors.
+    #[arg(long, global = true)]
+    strict: bool,
 }
 
 /// Commands available.
 #[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)]
 enum Command {
     /// Build landscape website.
     Build(BuildArgs),
 
+    /// Check the links referenced from the landscape data are reachable.
+    CheckLinks(CheckLinksArgs),
+
     /// Deploy landscape website (experimental).
     Deploy(DeployArgs),
 
+    /// Create a new landscape scaffold with some starter data and settings.
+    New(NewArgs),
+
+    /// Query the landscape data using a JMESPath expression.
+    Query(QueryArgs),
+
+    /// Print the JSON Schema of the landscape data sources files.
+    Schema(SchemaArgs),
+
     /// Validate landscape data sources files.
     Validate(ValidateArgs),
 }
 
 /// Build command arguments.
 #[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
 struct BuildArgs {
-    /// Cache directory.
+    /// Check the rendered index document for some accessibility issues (i.e.
+    /// images without alternative text or missing document language).
+    #[arg(long)]
+    a11y_check: bool,
+
+    /// Allow building the landscape when the data source contains no items.
+    #[arg(long)]
+    allow_empty: bool,
+
+    /// Package the output directory into a .tar.gz archive written next to it.
+    #[arg(long)]
+    archive: bool,
+
+    /// Base url where the landscape assets (datasets, logos and images) will
+    /// be served from (i.e. a CDN). The references to them in the datasets
+    /// and the index document are rewritten to absolute urls under it, but
+    /// the files are still written to the output directory for upload.
+    #[arg(long, conflicts_with = "single_file")]
+    assets_base_url: Option<String>,
+
+    /// Base href of the landscape website (i.e. https://landscape.cncf.io/).
+    #[arg(long)]
+    base_href: Option<String>,
+
+    /// Collect the number of followers of the items Bluesky accounts.
+    #[arg(long)]
+    bluesky: bool,
+
+    /// Landscape bundle (.tar.gz) containing the data file, settings, logos
+    /// and guide. When provided, the other sources must not be used.
+    #[arg(long, groups = ["DataSource", "GuideSource", "LogosSource", "SettingsSource"])]
+    bundle: Option<PathBuf>,
+
+    /// Cache backend.
+    #[arg(long, value_enum, default_value_t = CacheBackend::Fs)]
+    cache_backend: CacheBackend,
+
+    /// Cache directory (fs cache backend).
     #[arg(long)]
     cache_dir: Option<PathBuf>,
 
+    /// Namespace used to store the cache files, so that multiple landscapes
+    /// can share the same cache without collisions.
+    #[arg(long)]
+    cache_namespace: Option<String>,
+
+    /// Use the cache in read-only mode: cached data is reused, but nothing
+    /// is written back to the cache (i.e. when it is mounted read-only).
+    #[arg(long)]
+    cache_read_only: bool,
+
+    /// AWS S3 bucket where the cache files will be stored (s3 cache backend).
+    #[arg(long)]
+    cache_s3_bucket: Option<String>,
+
+    /// Generate a markdown file for each category (docs/categories/<slug>.md)
+    /// with a table listing its items.
+    #[arg(long)]
+    categories_md: bool,
+
+    /// Comma separated list of items (ids or names) whose external data should
+    /// be refreshed. The data of the rest of the items is served from the cache
+    /// (even if it has expired).
+    #[arg(long, value_delimiter = ',')]
+    changed_items: Option<Vec<String>>,
+
     /// Data source.
     #[command(flatten)]
     data_source: DataSource,
 
+    /// Only generate the datasets (the web application assets, the index
+    /// document and the csv/md files will not be generated).
+    #[arg(long)]
+    datasets_only: bool,
+
+    /// SVG logo to use for the items whose logo could not be prepared.
+    #[arg(long)]
+    default_logo: Option<PathBuf>,
+
+    /// Write the raw data collected from external services (Crunchbase,
+    /// GitHub, crates.io, Scorecard and social networks) to the output
+    /// directory (debug) before merging it.
+    #[arg(long)]
+    dump_external_data: bool,
+
+    /// Embed the guide in the index document instead of loading it from a
+    /// separate file.
+    #[arg(long)]
+    embed_guide: bool,
+
+    /// Error page (404.html) to copy to the output directory instead of the
+    /// default one.
+    #[arg(long)]
+    error_page: Option<PathBuf>,
+
+    /// Export a graph of the landscape items, organizations and categories.
+    #[arg(long, value_enum)]
+    export_graph: Option<GraphFormat>,
+
+    /// Use the item's homepage favicon as logo when it cannot be prepared.
+    #[arg(long)]
+    favicon_fallback: bool,
+
+    /// Generate a feeds.opml file with the feeds of the items that provide one.
+    #[arg(long)]
+    feeds_opml: bool,
+
+    /// Generate a QR code linking to each item page (requires --base-href).
+    #[arg(long, requires = "base_href")]
+    generate_qr: bool,
+
+    /// Collect the repositories issues and pull requests activity during the
+    /// number of days provided (90 if no value is provided).
+    #[arg(long, num_args = 0..=1, default_missing_value = "90")]
+    github_activity_days: Option<i64>,
+
+    /// Collect the repositories weekly number of commits during the last year.
+    #[arg(long)]
+    github_commit_activity: bool,
+
+    /// GitHub API base url (i.e. to use a GitHub Enterprise Server instance).
+    #[arg(long)]
+    github_base_url: Option<String>,
+
+    /// File containing a comma (or newline) separated list of GitHub tokens.
+    /// When provided, it takes precedence over the GITHUB_TOKENS environment
+    /// variable.
+    #[arg(long)]
+    github_tokens_file: Option<PathBuf>,
+
     /// Guide source.
     #[command(flatten)]
     guide_source: GuideSource,
 
+    /// Remove the items that have reached their end of life date from the
+    /// landscape (otherwise they are flagged as such in the datasets).
+    #[arg(long)]
+    hide_eol: bool,
+
+    /// Generate the history of the items added to and removed from the
+    /// landscape (history.json) from the commits of the Git repository the
+    /// local data file belongs to.
+    #[arg(long, requires = "data_file")]
+    history: bool,
+
+    /// Include a JSON-LD structured data block describing the landscape and
+    /// its items in the index document (for SEO).
+    #[arg(long)]
+    json_ld: bool,
+
+    /// Generate a llms.txt file summarizing the landscape categories and items.
+    #[arg(long)]
+    llms_txt: bool,
+
+    /// Only prepare the items logos, writing them to the output directory
+    /// along with a file mapping each item to its logo (the rest of the build
+    /// steps, like collecting data from external services, will be skipped).
+    #[arg(long)]
+    logos_only: bool,
+
+    /// Shard the logos in the output directory into subdirectories named after
+    /// the first N (1 to 4) characters of their digest (i.e.
+    /// logos/ab/abcdef...svg).
+    #[arg(long, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..=4))]
+    logos_shard_length: Option<usize>,
+
     /// Logos source.
     #[command(flatten)]
     logos_source: LogosSource,
 
+    /// Maximum number of tasks (i.e. logos being prepared or requests to
+    /// external services) that can run concurrently across the whole build.
+    #[arg(long)]
+    max_concurrency: Option<usize>,
+
+    /// Maximum total size (in bytes) of the output directory. The build will
+    /// fail if it is exceeded.
+    #[arg(long)]
+    max_output_size: Option<u64>,
+
+    /// Collect the number of followers of the items Mastodon accounts.
+    #[arg(long)]
+    mastodon: bool,
+
+    /// File to write the build metrics to, in the Prometheus text exposition
+    /// format (i.e. to be collected by the node exporter textfile collector).
+    #[arg(long)]
+    metrics_file: Option<PathBuf>,
+
+    /// Report the items whose primary repository has fewer GitHub stars than
+    /// the minimum provided, so that they can be reviewed (they are not
+    /// removed from the landscape).
+    #[arg(long)]
+    min_stars: Option<i64>,
+
     /// Output directory to write files to.
     #[arg(long)]
     output_dir: PathBuf,
 
+    /// Shell command to run after a successful build (the output directory
+    /// path is available in the LANDSCAPE_OUTPUT_DIR environment variable).
+    #[arg(long)]
+    post_build_command: Option<String>,
+
+    /// Check the external services configured (GitHub and Crunchbase) are
+    /// reachable and authorized, and exit without building the landscape.
+    #[arg(long)]
+    preflight: bool,
+
+    /// Pretty-print the datasets JSON files (useful when debugging).
+    #[arg(long)]
+    pretty_datasets: bool,
+
+    /// Custom template used to generate the projects.md file (Jinja2 syntax).
+    #[arg(long)]
+    projects_template: Option<PathBuf>,
+
+    /// Remove the cache entries not referenced by the landscape after the build.
+    #[arg(long)]
+    prune_cache: bool,
+
+    /// Remove the logos in the output directory not referenced by any of the
+    /// current items (i.e. left behind by previous builds).
+    #[arg(long)]
+    prune_output_logos: bool,
+
+    /// Render the items descriptions (markdown) to sanitized HTML, adding it
+    /// to the items in the description_html field.
+    #[arg(long)]
+    render_descriptions: bool,
+
+    /// Collect the OpenSSF Scorecard results of the GitHub repositories.
+    #[arg(long)]
+    scorecard: bool,
+
     /// Settings source.
     #[command(flatten)]
     settings_source: SettingsSource,
+
+    /// Also split the full dataset into the number of shards provided
+    /// (full-N.json), along with an index file (full-index.json), so that
+    /// they can be fetched in parallel.
+    #[arg(long, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    shard_full: Option<usize>,
+
+    /// Generate a single self-contained index document, with the web
+    /// application assets, the datasets and the logos inlined (this is not
+    /// recommended for large landscapes, as the document may get quite big).
+    #[arg(long)]
+    single_file: bool,
+
+    /// Sort the items in the datasets and exported files using the key provided.
+    #[arg(long, value_enum)]
+    sort_by: Option<SortBy>,
+
+    /// Also generate a dataset file per category (data/categories/<slug>.json)
+    /// containing only that category's items.
+    #[arg(long)]
+    split_by_category: bool,
+
+    /// Fail as soon as any of the settings images cannot be processed.
+    #[arg(long)]
+    strict_settings_images: bool,
+
+    /// Report the time taken by each of the build phases.
+    #[arg(long)]
+    timings: bool,
+
+    /// Command used to transform the landscape data before generating the
+    /// datasets. The data is written to the command's stdin in JSON format,
+    /// and the transformed data is expected in the same format on its stdout.
+    #[arg(long)]
+    transform: Option<String>,
+
+    /// Verify the output directory contains all the files required.
+    #[arg(long)]
+    verify_output: bool,
+
+    /// Rebuild the landscape website every time any of the local sources
+    /// provided changes (data file, settings file, guide file or logos).
+    #[arg(long)]
+    watch: bool,
+
+    /// Generate an embeddable widget (widget.html) displaying the items in the
+    /// category provided, along with its dataset (data/widget.json).
+    #[arg(long)]
+    widget_category: Option<String>,
+}
+
+/// Backend used to store the cache files.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum CacheBackend {
+    Fs,
+    Memory,
+    S3,
+}
+
+/// Check links command arguments.
+#[derive(Args)]
+struct CheckLinksArgs {
+    /// Maximum number of links checked concurrently.
+    #[arg(long, default_value_t = 10)]
+    concurrency: usize,
+
+    /// Data source.
+    #[command(flatten)]
+    data_source: DataSource,
+
+    /// Format of the report.
+    #[arg(long, value_enum, default_value_t = LinksReportFormat::Text)]
+    format: LinksReportFormat,
+
+    /// Base URL where the logos are hosted (logos are only checked when it is
+    /// provided).
+    #[arg(long)]
+    logos_url: Option<String>,
+
+    /// Time to wait for each of the links to respond (in seconds).
+    #[arg(long, default_value_t = 30)]
+    timeout: u64,
+}
+
+/// Format of the links check report.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum LinksReportFormat {
+    Json,
+    Text,
+}
+
+/// Format used to export the landscape graph.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum GraphFormat {
+    Dot,
+    Graphml,
 }
 
 /// Landscape data location.
@@ -87,6 +423,16 @@ struct GuideSource {
     guide_url: Option<String>,
 }
 
+/// Key used to sort the landscape items.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum SortBy {
+    #[value(name = "added_at")]
+    AddedAt,
+    Category,
+    Name,
+    Stars,
+}
+
 /// Landscape logos location.
 #[derive(Args, Clone)]
 #[group(required = true, multiple = false)]
@@ -141,6 +487,55 @@ struct S3Args {
     landscape_dir: PathBuf,
 }
 
+/// New command arguments.
+#[derive(Args)]
+struct NewArgs {
+    /// Directory where the landscape scaffold will be created.
+    #[arg(long)]
+    output_dir: PathBuf,
+}
+
+/// Query command arguments.
+#[derive(Args)]
+struct QueryArgs {
+    /// Data source.
+    #[command(flatten)]
+    data_source: DataSource,
+
+    /// JMESPath expression to apply to the landscape data (i.e.
+    /// "items[?category=='Provisioning'].name"). Only the information in the
+    /// landscape data file can be queried: the data collected from external
+    /// services (i.e. GitHub stars or Crunchbase funding) is not available.
+    expression: String,
+}
+
+/// Schema command arguments.
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+struct SchemaArgs {
+    /// Landscape file to print the JSON Schema for.
+    #[command(subcommand)]
+    target: SchemaTarget,
+}
+
+/// Landscape file to print the JSON Schema for.
+#[derive(Subcommand)]
+enum SchemaTarget {
+    /// Print landscape data file JSON Schema.
+    Data(SchemaOutputArgs),
+
+    /// Print landscape settings file JSON Schema.
+    Settings(SchemaOutputArgs),
+}
+
+/// Schema output arguments.
+#[derive(Args)]
+struct SchemaOutputArgs {
+    /// File to write the JSON Schema to (stdout is used when not provided).
+    #[arg(long)]
+    output_file: Option<PathBuf>,
+}
+
 /// Validate command arguments.
 #[derive(Args)]
 #[command(args_conflicts_with_subcommands = true)]
@@ -154,7 +549,42 @@ struct ValidateArgs {
 #[derive(Subcommand)]
 enum ValidateTarget {
     /// Validate landscape data file.
-    Data(DataSource),
+    Data(ValidateDataArgs),
+
+    /// Validate landscape settings file.
+    Settings(ValidateSettingsArgs),
+}
+
+/// Validate data command arguments.
+#[derive(Args)]
+struct ValidateDataArgs {
+    /// Check that the data file is in canonical YAML form.
+    #[arg(long)]
+    check_format: bool,
+
+    /// Data source.
+    #[command(flatten)]
+    data_source: DataSource,
+
+    /// Check that the items logos files exist in the local path provided.
+    #[arg(long)]
+    logos_path: Option<PathBuf>,
+
+    /// YAML file with custom validation rules to apply to the items.
+    #[arg(long)]
+    rules_file: Option<PathBuf>,
+
+    /// Rewrite the data file in canonical YAML form (implies --check-format).
+    #[arg(long)]
+    write: bool,
+}
+
+/// Validate settings command arguments.
+#[derive(Args)]
+struct ValidateSettingsArgs {
+    /// Settings source.
+    #[command(flatten)]
+    settings_source: SettingsSource,
 }
 
 #[tokio::main]
@@ -172,18 +602,35 @@ async fn main() -> Result<()> {
     match &cli.command {
         Command::Build(args) => {
             setup_logging();
-            build(args).await?;
+            if args.preflight {
+                preflight(args).await?;
+            } else if args.watch {
+                watch(args).await?;
+            } else {
+                build(args, cli.strict).await?;
+            }
         }
+        Command::CheckLinks(args) => check_links(args).await?,
         Command::Deploy(args) => {
             setup_logging();
             match &args.provider {
                 Provider::S3(args) => s3::deploy(args).await?,
             };
         }
+        Command::New(args) => new_landscape(args)?,
+        Command::Query(args) => query_data(args).await?,
+        Command::Schema(args) => match &args.target {
+            SchemaTarget::Data(args) => schema::print_data_schema(args)?,
+            SchemaTarget::Settings(args) => schema::print_settings_schema(args)?,
+        },
         Command::Validate(args) => match &args.target {
-            ValidateTarget::Data(src) => validate_data(src).await?,
+            ValidateTarget::Data(args) => validate_data(args).await?,
+            ValidateTarget::Settings(args) => validate_settings(args).await?,
         },
     }
 
+    // Report warnings collected while running the command
+    warnings::report(cli.strict)?;
+
     Ok(())
 }
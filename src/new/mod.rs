@@ -0,0 +1,59 @@
+//! This module defines the functionality of the new CLI subcommand.
+
+use crate::NewArgs;
+use anyhow::{format_err, Result};
+use std::{fs, path::Path};
+use tracing::{debug, instrument};
+
+/// Files included in the landscape scaffold (path and content).
+const SCAFFOLD_FILES: [(&str, &str); 3] = [
+    ("landscape.yml", include_str!("template/landscape.yml")),
+    ("settings.yml", include_str!("template/settings.yml")),
+    ("logos/sample.svg", include_str!("template/logos/sample.svg")),
+];
+
+/// Create a new landscape scaffold in the output directory provided. The
+/// scaffold contains a minimal valid data file, a settings file and a sample
+/// logo, so that it can be built right away. Existing files are never
+/// overwritten.
+#[instrument(skip_all, err)]
+pub(crate) fn new_landscape(args: &NewArgs) -> Result<()> {
+    let output_dir = &args.output_dir;
+
+    // Check none of the scaffold files exist yet
+    for (path, _) in SCAFFOLD_FILES {
+        if output_dir.join(path).exists() {
+            return Err(format_err!(
+                "{} already exists, refusing to overwrite it",
+                output_dir.join(path).display()
+            ));
+        }
+    }
+
+    // Write scaffold files
+    for (path, content) in SCAFFOLD_FILES {
+        let path = output_dir.join(path);
+        debug!(?path, "writing scaffold file");
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)?;
+    }
+
+    println!("Landscape scaffold created in {}!", output_dir.display());
+    println!("\nYou can build it by running:\n\n{}", build_command(output_dir));
+
+    Ok(())
+}
+
+/// Return the build command that can be used to build the scaffold created in
+/// the directory provided.
+fn build_command(dir: &Path) -> String {
+    format!(
+        "  landscape2 build --data-file {} --settings-file {} --logos-path {} --output-dir {}",
+        dir.join("landscape.yml").display(),
+        dir.join("settings.yml").display(),
+        dir.join("logos").display(),
+        dir.join("build").display(),
+    )
+}
@@ -0,0 +1,27 @@
+//! This module defines the functionality of the query CLI subcommand.
+
+use crate::{build::LandscapeData, QueryArgs};
+use anyhow::{format_err, Context, Result};
+use tracing::instrument;
+
+/// Query the landscape data using the JMESPath expression provided, printing
+/// the result in JSON format. Only the source data is queried (the data
+/// collected from external services is not added to it).
+#[instrument(skip_all, err)]
+pub(crate) async fn query_data(args: &QueryArgs) -> Result<()> {
+    // Compile expression
+    let expr = jmespath::compile(&args.expression).map_err(|err| format_err!("invalid expression: {err}"))?;
+
+    // Get landscape data from the source provided
+    let landscape_data = LandscapeData::new(&args.data_source)
+        .await
+        .context("the landscape data file provided is not valid")?;
+
+    // Apply expression to the landscape data and print the result
+    let data = jmespath::Variable::from_serializable(landscape_data)
+        .map_err(|err| format_err!("error preparing landscape data: {err}"))?;
+    let result = expr.search(data).map_err(|err| format_err!("error running query: {err}"))?;
+    println!("{}", serde_json::to_string_pretty(&result)?);
+
+    Ok(())
+}
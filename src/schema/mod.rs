@@ -0,0 +1,37 @@
+//! This module defines the functionality of the schema CLI subcommand.
+
+use crate::{
+    build::{LandscapeData, LandscapeSettings},
+    SchemaOutputArgs,
+};
+use anyhow::Result;
+use schemars::{schema::RootSchema, schema_for};
+use std::{fs, io::Write};
+use tracing::instrument;
+
+/// Print the JSON Schema of the landscape data file.
+#[instrument(skip_all, err)]
+pub(crate) fn print_data_schema(args: &SchemaOutputArgs) -> Result<()> {
+    print_schema(&LandscapeData::json_schema(), args)
+}
+
+/// Print the JSON Schema of the landscape settings file.
+#[instrument(skip_all, err)]
+pub(crate) fn print_settings_schema(args: &SchemaOutputArgs) -> Result<()> {
+    print_schema(&schema_for!(LandscapeSettings), args)
+}
+
+/// Write the schema provided to the output file (or stdout if no output file
+/// was provided).
+fn print_schema(schema: &RootSchema, args: &SchemaOutputArgs) -> Result<()> {
+    let mut json_data = serde_json::to_vec_pretty(schema)?;
+    json_data.push(b'\n');
+
+    if let Some(output_file) = &args.output_file {
+        fs::write(output_file, json_data)?;
+    } else {
+        std::io::stdout().write_all(&json_data)?;
+    }
+
+    Ok(())
+}
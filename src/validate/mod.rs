@@ -1,16 +1,129 @@
 //! This module defines the functionality of the validate CLI subcommand.
 
-use crate::{build::LandscapeData, DataSource};
-use anyhow::{Context, Result};
+use crate::{
+    build::{LandscapeData, LandscapeSettings},
+    ValidateDataArgs, ValidateSettingsArgs,
+};
+use anyhow::{format_err, Context, Result};
+use rules::RulesFile;
+use std::{fs, path::Path};
 use tracing::instrument;
 
+mod rules;
+
 /// Validate landscape data file.
 #[instrument(skip_all)]
-pub(crate) async fn validate_data(data_source: &DataSource) -> Result<()> {
-    LandscapeData::new(data_source)
+pub(crate) async fn validate_data(args: &ValidateDataArgs) -> Result<()> {
+    let landscape_data = LandscapeData::new(&args.data_source)
         .await
         .context("the landscape data file provided is not valid")?;
 
     println!("The landscape data file provided is valid!");
+
+    // Check items logos can be located if requested
+    if let Some(logos_path) = &args.logos_path {
+        check_items_logos(&landscape_data, logos_path)?;
+    }
+
+    // Apply custom validation rules if provided
+    if let Some(rules_file) = &args.rules_file {
+        check_custom_rules(&landscape_data, rules_file)?;
+    }
+
+    // Check data file format if requested
+    if args.check_format || args.write {
+        let Some(file) = &args.data_source.data_file else {
+            return Err(format_err!(
+                "checking the format is only supported for local data files"
+            ));
+        };
+        check_data_file_format(file, args.write)?;
+    }
+
     Ok(())
 }
+
+/// Validate landscape settings file.
+#[instrument(skip_all)]
+pub(crate) async fn validate_settings(args: &ValidateSettingsArgs) -> Result<()> {
+    LandscapeSettings::new(&args.settings_source)
+        .await
+        .context("the landscape settings file provided is not valid")?;
+
+    println!("The landscape settings file provided is valid!");
+
+    Ok(())
+}
+
+/// Check that the logo file of each of the items exists in the logos path
+/// provided (logos are not read or processed).
+fn check_items_logos(landscape_data: &LandscapeData, logos_path: &Path) -> Result<()> {
+    let missing_logos: Vec<String> = landscape_data
+        .items
+        .iter()
+        .filter(|item| !logos_path.join(&item.logo).is_file())
+        .map(|item| format!("- {}: {}", item.name, item.logo))
+        .collect();
+
+    if missing_logos.is_empty() {
+        println!("All items logos were found in the logos path provided!");
+        return Ok(());
+    }
+
+    Err(format_err!(
+        "some items logos were not found in the logos path provided:\n{}",
+        missing_logos.join("\n")
+    ))
+}
+
+/// Check that the landscape data items satisfy the custom validation rules
+/// defined in the rules file provided.
+fn check_custom_rules(landscape_data: &LandscapeData, rules_file: &Path) -> Result<()> {
+    let rules_file = RulesFile::new(rules_file)?;
+    let findings: Vec<String> = rules_file
+        .apply(landscape_data)?
+        .into_iter()
+        .map(|finding| {
+            format!(
+                "- [{}] {}: {}",
+                finding.rule_id, finding.item_name, finding.message
+            )
+        })
+        .collect();
+
+    if findings.is_empty() {
+        println!("All custom validation rules passed!");
+        return Ok(());
+    }
+
+    Err(format_err!(
+        "some items do not satisfy the custom validation rules:\n{}",
+        findings.join("\n")
+    ))
+}
+
+/// Check that the landscape data file provided is in canonical YAML form,
+/// rewriting it in place when requested.
+///
+/// The canonical form is obtained by parsing the data file and serializing it
+/// back to YAML. The order of the fields is preserved, but comments are not.
+fn check_data_file_format(file: &Path, write: bool) -> Result<()> {
+    let raw_data = fs::read_to_string(file)?;
+    let data: serde_yaml::Value = serde_yaml::from_str(&raw_data)?;
+    let canonical_data = serde_yaml::to_string(&data)?;
+
+    if raw_data == canonical_data {
+        println!("The landscape data file provided is in canonical form!");
+        return Ok(());
+    }
+
+    if write {
+        fs::write(file, canonical_data)?;
+        println!("The landscape data file provided has been rewritten in canonical form!");
+        return Ok(());
+    }
+
+    Err(format_err!(
+        "the landscape data file provided is not in canonical form (use --write to reformat it)"
+    ))
+}
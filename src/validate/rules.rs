@@ -0,0 +1,200 @@
+//! This module defines the custom validation rules that can be provided in a
+//! rules file, as well as the functionality used to apply them to the
+//! landscape data items.
+//!
+//! Rules are applied to the items as they are represented once the landscape
+//! data file has been processed. Fields are referenced by their name, and
+//! nested fields can be referenced using dots (i.e. `repositories.0.url`).
+
+use crate::build::LandscapeData;
+use anyhow::{format_err, Context, Result};
+use regex::Regex;
+use serde::Deserialize;
+use std::{fs, path::Path};
+
+/// Custom validation rules file.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct RulesFile {
+    pub rules: Vec<Rule>,
+}
+
+impl RulesFile {
+    /// Create a new rules file instance from the YAML file provided.
+    pub(crate) fn new(path: &Path) -> Result<Self> {
+        let raw_data = fs::read_to_string(path).context("error reading rules file")?;
+        let rules_file: RulesFile = serde_yaml::from_str(&raw_data).context("invalid rules file")?;
+        rules_file.validate()?;
+
+        Ok(rules_file)
+    }
+
+    /// Validate the rules provided.
+    fn validate(&self) -> Result<()> {
+        let mut ids = vec![];
+        for rule in &self.rules {
+            if rule.id.trim().is_empty() {
+                return Err(format_err!("rules ids cannot be empty"));
+            }
+            if ids.contains(&&rule.id) {
+                return Err(format_err!("duplicate rule id: {}", rule.id));
+            }
+            ids.push(&rule.id);
+
+            match &rule.check {
+                Check::ForbiddenPattern { pattern, .. } => {
+                    Regex::new(pattern).context(format!("rule {}: invalid pattern", rule.id))?;
+                }
+                Check::Count { min, max, .. } => {
+                    if min.is_none() && max.is_none() {
+                        return Err(format_err!("rule {}: min or max must be provided", rule.id));
+                    }
+                    if let (Some(min), Some(max)) = (min, max) {
+                        if min > max {
+                            return Err(format_err!("rule {}: min cannot be greater than max", rule.id));
+                        }
+                    }
+                }
+                Check::RequiredField { .. } => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Apply the rules to the landscape data items, returning the findings
+    /// reported by each of them.
+    pub(crate) fn apply(&self, landscape_data: &LandscapeData) -> Result<Vec<Finding>> {
+        // Serialize the items once for all rules
+        let items_json =
+            landscape_data.items.iter().map(serde_json::to_value).collect::<Result<Vec<_>, _>>()?;
+
+        let mut findings = vec![];
+        for rule in &self.rules {
+            // Compile the rule's pattern (if any) once for all items
+            let pattern = match &rule.check {
+                Check::ForbiddenPattern { pattern, .. } => Some(Regex::new(pattern)?),
+                _ => None,
+            };
+
+            for (item, item_json) in landscape_data.items.iter().zip(&items_json) {
+                if rule.category.as_ref().is_some_and(|category| category != &item.category) {
+                    continue;
+                }
+
+                let value = item_json
+                    .pointer(&format!("/{}", rule.check.field().replace('.', "/")))
+                    .filter(|value| !value.is_null());
+                let issue = match &rule.check {
+                    Check::Count { field, min, max } => {
+                        let count = match value {
+                            Some(serde_json::Value::Array(values)) => values.len(),
+                            Some(_) => 1,
+                            None => 0,
+                        };
+                        if min.is_some_and(|min| count < min) || max.is_some_and(|max| count > max) {
+                            Some(format!(
+                                "{field} has {count} entries ({})",
+                                count_range(*min, *max)
+                            ))
+                        } else {
+                            None
+                        }
+                    }
+                    Check::ForbiddenPattern { field, .. } => {
+                        let pattern = pattern.as_ref().expect("pattern to be compiled");
+                        let matches = match value {
+                            Some(serde_json::Value::String(value)) => pattern.is_match(value),
+                            Some(serde_json::Value::Array(values)) => {
+                                values.iter().any(|v| v.as_str().is_some_and(|v| pattern.is_match(v)))
+                            }
+                            _ => false,
+                        };
+                        matches.then(|| format!("{field} matches forbidden pattern {}", pattern.as_str()))
+                    }
+                    Check::RequiredField { field } => {
+                        let is_empty = match value {
+                            Some(serde_json::Value::String(value)) => value.trim().is_empty(),
+                            Some(serde_json::Value::Array(values)) => values.is_empty(),
+                            Some(_) => false,
+                            None => true,
+                        };
+                        is_empty.then(|| format!("{field} is required"))
+                    }
+                };
+
+                if let Some(issue) = issue {
+                    findings.push(Finding {
+                        rule_id: rule.id.clone(),
+                        item_name: item.name.clone(),
+                        message: rule.message.clone().unwrap_or(issue),
+                    });
+                }
+            }
+        }
+
+        Ok(findings)
+    }
+}
+
+/// Return a description of the range of entries allowed by a count rule.
+fn count_range(min: Option<usize>, max: Option<usize>) -> String {
+    match (min, max) {
+        (Some(min), Some(max)) => format!("expected between {min} and {max}"),
+        (Some(min), None) => format!("expected at least {min}"),
+        (None, Some(max)) => format!("expected at most {max}"),
+        (None, None) => String::new(),
+    }
+}
+
+/// Custom validation rule.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Rule {
+    pub id: String,
+    pub check: Check,
+
+    /// Only apply the rule to the items in this category.
+    pub category: Option<String>,
+
+    /// Message reported instead of the default one when the check fails.
+    pub message: Option<String>,
+}
+
+/// Check performed by a validation rule.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub(crate) enum Check {
+    /// The number of entries of the field (i.e. tags) must be within the
+    /// limits provided.
+    Count {
+        field: String,
+        min: Option<usize>,
+        max: Option<usize>,
+    },
+
+    /// The field value (or any of its values) must not match the pattern.
+    ForbiddenPattern { field: String, pattern: String },
+
+    /// The field must be present and not empty.
+    RequiredField { field: String },
+}
+
+impl Check {
+    /// Return the field the check applies to.
+    fn field(&self) -> &str {
+        match self {
+            Check::Count { field, .. }
+            | Check::ForbiddenPattern { field, .. }
+            | Check::RequiredField { field } => field,
+        }
+    }
+}
+
+/// Finding reported by a validation rule.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Finding {
+    pub rule_id: String,
+    pub item_name: String,
+    pub message: String,
+}
@@ -16,7 +16,11 @@ const Layout = (props: Props) => {
   return (
     <AppContextProvider foundation={props.data.foundation}>
       <div className="h-100 d-flex flex-column">
-        <Header logo={props.data.images.header_logo} items={props.data.items} />
+        <Header
+          logo={props.data.images.header_logo}
+          logoSrcset={props.data.images.header_logo_srcset}
+          items={props.data.items}
+        />
         <div className="d-flex flex-column flex-grow-1">
           <div className="d-block d-lg-none mx-5">
             <NoData>
@@ -30,7 +34,7 @@ const Layout = (props: Props) => {
             <Outlet />
           </main>
         </div>
-        <Footer logo={props.data.images.footer_logo} />
+        <Footer logo={props.data.images.footer_logo} logoSrcset={props.data.images.footer_logo_srcset} />
         <ItemModal />
         <ZoomModal />
       </div>
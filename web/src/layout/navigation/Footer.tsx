@@ -1,12 +1,14 @@
 import { isEmpty, isUndefined } from 'lodash';
 
 import { SVGIconKind } from '../../types';
+import getImageSrcset from '../../utils/getImageSrcset';
 import ExternalLink from '../common/ExternalLink';
 import SVGIcon from '../common/SVGIcon';
 import styles from './Footer.module.css';
 
 interface Props {
   logo?: string;
+  logoSrcset?: string;
 }
 
 const Footer = (props: Props) => {
@@ -111,6 +113,7 @@ const Footer = (props: Props) => {
                       className={styles.logo}
                       alt="Logo"
                       src={import.meta.env.MODE === 'development' ? `../../static/${props.logo}` : `${props.logo}`}
+                      srcSet={getImageSrcset(props.logoSrcset)}
                     />
                   </div>
                 )}
@@ -2,6 +2,7 @@ import { NavLink } from 'react-router-dom';
 import { Link } from 'react-router-dom';
 
 import { BaseItem, SVGIconKind } from '../../types';
+import getImageSrcset from '../../utils/getImageSrcset';
 import ExternalLink from '../common/ExternalLink';
 import Searchbar from '../common/Searchbar';
 import SVGIcon from '../common/SVGIcon';
@@ -9,6 +10,7 @@ import styles from './Header.module.css';
 
 interface Props {
   logo: string;
+  logoSrcset?: string;
   items: BaseItem[];
 }
 
@@ -21,6 +23,7 @@ const Header = (props: Props) => {
             <img
               className={styles.logo}
               src={import.meta.env.MODE === 'development' ? `../../static/${props.logo}` : `${props.logo}`}
+              srcSet={getImageSrcset(props.logoSrcset)}
               alt="Landscape logo"
             />
           </Link>
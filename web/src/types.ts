@@ -1,8 +1,11 @@
 export interface BaseData {
+  assets_base_url?: string;
   foundation: string;
   images: {
     footer_logo?: string;
+    footer_logo_srcset?: string;
     header_logo: string;
+    header_logo_srcset?: string;
   };
   colors?: {
     [key: string]: string;
@@ -12,6 +15,7 @@ export interface BaseData {
   categories: Category[];
   categories_overridden?: string[];
   items: BaseItem[];
+  maturity_levels?: string[];
   members_category?: string;
   social_networks?: {
     facebook?: string;
@@ -25,6 +29,13 @@ export interface BaseData {
     wechat?: string;
     youtube?: string;
   };
+  tags?: Tag[];
+}
+
+export interface Tag {
+  name: string;
+  color?: string;
+  display_name?: string;
 }
 
 export interface Group {
@@ -45,13 +56,22 @@ export interface BaseItem {
   logo: string;
   subcategory: string;
   description?: string;
+  eol?: boolean;
   featured?: Featured;
+  language?: string;
+  latest_commit?: string;
+  license?: string;
   maturity?: string;
+  parent?: string;
+  stars?: number;
+  tags?: string[];
+  topics?: string[];
 }
 
 export interface Featured {
   label?: string;
   order?: number;
+  weight?: number;
 }
 
 export interface Item extends BaseItem {
@@ -59,23 +79,35 @@ export interface Item extends BaseItem {
   homepage_url?: string;
   artwork_url?: string;
   blog_url?: string;
+  bluesky_data?: SocialAccount;
+  bluesky_url?: string;
   chat_channel?: string;
   clomonitor_name?: string;
   member_subcategory?: string;
+  crate_name?: string;
+  cratesio_data?: Crate;
   crunchbase_data?: Organization;
+  description_html?: string;
   crunchbase_url?: string;
   devstats_url?: string;
   discord_url?: string;
   docker_url?: string;
   enduser?: boolean;
+  eol_at?: string;
+  feed_url?: string;
   github_discussions_url?: string;
   graduated_at?: string;
   incubating_at?: string;
   joined_at?: string;
+  logo_dominant_color?: string;
   mailing_list_url?: string;
+  mastodon_data?: SocialAccount;
+  mastodon_url?: string;
+  metadata?: { [key: string]: unknown };
   latest_annual_review_at?: string;
   latest_annual_review_url?: string;
   openssf_best_practices_url?: string;
+  qr_code?: string;
   repositories?: Repository[];
   slack_url?: string;
   specification?: boolean;
@@ -86,6 +118,18 @@ export interface Item extends BaseItem {
   youtube_url?: string;
 }
 
+export interface Crate {
+  downloads: number;
+  generated_at: string;
+  latest_version: string;
+  recent_downloads?: number;
+}
+
+export interface SocialAccount {
+  followers: number;
+  generated_at: string;
+}
+
 export interface Organization {
   city?: string;
   company_type?: string;
@@ -122,9 +166,18 @@ export interface Repository {
   branch?: string;
   github_data?: GithubRepository;
   primary: boolean;
+  scorecard?: Scorecard;
+}
+
+export interface Scorecard {
+  checks: { [key: string]: number };
+  date?: string;
+  generated_at: string;
+  score: number;
 }
 
 export interface GithubRepository {
+  commit_activity?: number[];
   contributors: Contributors;
   description: string;
   first_commit: Commit;
@@ -135,6 +188,7 @@ export interface GithubRepository {
   license: string;
   participation_stats: number[];
   stars: number;
+  topics?: string[];
   url: string;
 }
 
@@ -0,0 +1,13 @@
+// Get the srcset value of an image, prefixing its references with the static
+// path when running in development mode
+const getImageSrcset = (srcset?: string): string | undefined => {
+  if (srcset === undefined || import.meta.env.MODE !== 'development') {
+    return srcset;
+  }
+  return srcset
+    .split(',')
+    .map((entry: string) => `../../static/${entry.trim()}`)
+    .join(', ');
+};
+
+export default getImageSrcset;
@@ -15,8 +15,16 @@ export class ItemsDataGetter {
   }
 
   public init() {
-    if (!this.ready) {
-      fetch(import.meta.env.MODE === 'development' ? '../../static/full.json' : './data/full.json')
+    if (!this.ready && window.fullDS) {
+      // The full dataset may be embedded in the index document
+      this.landscapeData = { ...window.fullDS };
+      this.ready = true;
+      if (this.updateStatus) {
+        this.updateStatus.updateStatus(true);
+      }
+    } else if (!this.ready) {
+      const assetsBaseUrl = window.baseDS.assets_base_url || '.';
+      fetch(import.meta.env.MODE === 'development' ? '../../static/full.json' : `${assetsBaseUrl}/data/full.json`)
         .then((res) => res.json())
         .then((data: LandscapeData) => {
           this.landscapeData = { ...data };
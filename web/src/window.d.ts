@@ -1,7 +1,9 @@
-import { BaseData } from './types';
+import { BaseData, LandscapeData } from './types';
 
 declare global {
   interface Window {
     baseDS: BaseData;
+    fullDS?: LandscapeData;
+    guide?: object;
   }
 }